@@ -1,36 +1,522 @@
+use async_trait::async_trait;
+use futures::{Stream, stream};
 use miden_assembly::{
     Assembler, DefaultSourceManager, LibraryPath,
     ast::{Module, ModuleKind},
+    diagnostics::{Diagnostic, LabeledSpan, Report, SourceCode},
 };
 use miden_crypto::dsa::rpo_falcon512::Polynomial;
-use rand::{RngCore, rngs::StdRng};
-use std::sync::Arc;
-use tokio::time::{Duration, sleep};
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+use rand_chacha::ChaCha20Rng;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    sync::Semaphore,
+    time::{Duration, Instant, sleep},
+};
 
+#[cfg(feature = "testing")]
+use miden_client::note::NoteInclusionProof;
 use miden_client::{
     Client as MidenClient, ClientError, DebugMode, Felt, ScriptBuilder, Word,
     account::{
-        Account, AccountBuilder, AccountId, AccountStorageMode, AccountType,
+        Account, AccountBuilder, AccountComponent, AccountId, AccountStorageMode, AccountType,
+        StorageSlot,
         component::{BasicFungibleFaucet, BasicWallet},
     },
-    asset::{Asset, FungibleAsset, TokenSymbol},
-    auth::AuthSecretKey,
+    asset::{Asset, FungibleAsset, NonFungibleAsset, TokenSymbol},
+    auth::{AuthSecretKey, BasicAuthenticator, SigningInputs, TransactionAuthenticator},
+    block::{BlockHeader, BlockNumber},
     builder::ClientBuilder,
-    crypto::{FeltRng, SecretKey},
-    keystore::FilesystemKeyStore,
+    crypto::{FeltRng, RpoRandomCoin, SecretKey},
+    keystore::{FilesystemKeyStore, KeyStoreError},
     note::{
-        Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteInputs, NoteMetadata,
-        NoteRecipient, NoteScript, NoteTag, NoteType,
+        Note, NoteAssets, NoteDetails, NoteExecutionHint, NoteExecutionMode, NoteFile, NoteId,
+        NoteInputs, NoteMetadata, NoteRecipient, NoteScript, NoteTag, NoteType, Nullifier,
+    },
+    rpc::{
+        Endpoint, NodeRpcClient, RpcError, TonicRpcClient,
+        domain::{
+            account::{AccountProofs, FetchedAccount},
+            note::{FetchedNote, NoteSyncInfo},
+            nullifier::NullifierUpdate,
+            sync::StateSyncInfo,
+        },
+    },
+    store::{InputNoteRecord, NoteFilter, OutputNoteRecord},
+    sync::SyncSummary,
+    transaction::{
+        ForeignAccount, OutputNote, ProvenTransaction, TransactionFilter, TransactionId,
+        TransactionKernel, TransactionRecord, TransactionRequest, TransactionRequestBuilder,
+        TransactionRequestError, TransactionResult, TransactionScript, TransactionStatus,
     },
-    rpc::{Endpoint, TonicRpcClient},
-    store::NoteFilter,
-    transaction::{OutputNote, TransactionKernel, TransactionRequestBuilder, TransactionScript},
+    vm::AdviceInputs,
+};
+use miden_lib::{
+    MidenLib,
+    account::auth::{AuthRpoFalcon512, NoAuth},
+    note::{WellKnownNote, utils},
+};
+use miden_objects::{
+    AccountError, Hasher, MIN_PROOF_SECURITY_LEVEL, NoteError,
+    account::{AccountCode, AccountIdPrefix, AccountStorage},
+    assembly::Library,
+    block::ProvenBlock,
+    crypto::{
+        dsa::rpo_falcon512::PublicKey,
+        merkle::{MmrProof, SmtProof},
+    },
+    utils::{Deserializable, DeserializationError, Serializable},
+};
+use miden_processor::{
+    DefaultHost, ExecutionError, ExecutionOptions, StackInputs, execute as execute_program,
+};
+use miden_stdlib::StdLibrary;
+use miden_tx::{
+    AuthenticationError, LocalTransactionProver, ProvingOptions, TransactionExecutorError,
+    TransactionProverError, TransactionVerifier, TransactionVerifierError,
+    utils::sync::FutureMaybeSend,
 };
-use miden_lib::{account::auth::AuthRpoFalcon512, note::utils};
-use miden_objects::{Hasher, NoteError, assembly::Library};
 use serde::de::value::Error;
 
-type Client = MidenClient<FilesystemKeyStore<rand::prelude::StdRng>>;
+/// The keystore used by [`instantiate_client`]: a filesystem keystore backed by `StdRng`.
+pub type DefaultKeyStore = FilesystemKeyStore<StdRng>;
+
+/// A Miden client authenticating with `AUTH`, defaulting to [`DefaultKeyStore`].
+pub type Client<AUTH = DefaultKeyStore> = MidenClient<AUTH>;
+
+/// A store for account secret keys.
+///
+/// The helpers that create accounts are generic over this trait so that alternative keystores
+/// (in-memory, HSM-backed, ...) can be plugged in. Note that the client itself signs through its
+/// `TransactionAuthenticator`, so a custom keystore is usually also the client's authenticator.
+pub trait KeyStore: Clone {
+    /// Persists the given secret key.
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError>;
+
+    /// Returns all secret keys held by the keystore.
+    fn secret_keys(&self) -> Result<Vec<AuthSecretKey>, KeyStoreError>;
+}
+
+impl<R: Rng + Send + Sync> KeyStore for FilesystemKeyStore<R> {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        FilesystemKeyStore::add_key(self, key)
+    }
+
+    fn secret_keys(&self) -> Result<Vec<AuthSecretKey>, KeyStoreError> {
+        let entries = std::fs::read_dir(&self.keys_directory)
+            .map_err(|err| KeyStoreError::StorageError(err.to_string()))?;
+
+        let mut keys = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|err| KeyStoreError::StorageError(err.to_string()))?
+                .path();
+            if !path.is_file() {
+                continue;
+            }
+
+            // each file holds the hex-encoded serialized secret key
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|err| KeyStoreError::StorageError(err.to_string()))?;
+            let bytes = hex::decode(contents.trim())
+                .map_err(|err| KeyStoreError::DecodingError(err.to_string()))?;
+            let key = AuthSecretKey::read_from_bytes(&bytes)
+                .map_err(|err| KeyStoreError::DecodingError(err.to_string()))?;
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+}
+
+/// A keystore that keeps secret keys in memory and never touches the filesystem.
+///
+/// Clones share the same keys, so the keystore handed to the client as its authenticator sees
+/// every key added through the account creation helpers.
+#[derive(Clone, Default)]
+pub struct InMemoryKeyStore {
+    keys: Arc<Mutex<HashMap<Word, AuthSecretKey>>>,
+}
+
+impl InMemoryKeyStore {
+    /// Creates an empty keystore.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KeyStore for InMemoryKeyStore {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let AuthSecretKey::RpoFalcon512(secret_key) = key;
+        self.keys
+            .lock()
+            .unwrap()
+            .insert(secret_key.public_key().into(), key.clone());
+        Ok(())
+    }
+
+    fn secret_keys(&self) -> Result<Vec<AuthSecretKey>, KeyStoreError> {
+        Ok(self.keys.lock().unwrap().values().cloned().collect())
+    }
+}
+
+impl TransactionAuthenticator for InMemoryKeyStore {
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        signing_inputs: &SigningInputs,
+    ) -> impl FutureMaybeSend<Result<Vec<Felt>, AuthenticationError>> {
+        // sign through a short-lived `BasicAuthenticator` holding a snapshot of the keys, so the
+        // lock is not held across the await
+        let keys: Vec<(Word, AuthSecretKey)> = self
+            .keys
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(pub_key, key)| (*pub_key, key.clone()))
+            .collect();
+        async move {
+            BasicAuthenticator::<StdRng>::new_with_rng(&keys, StdRng::from_os_rng())
+                .get_signature(pub_key, signing_inputs)
+                .await
+        }
+    }
+}
+
+/// Errors returned by the helpers in this crate that can fail for reasons other than the client.
+#[derive(Debug)]
+pub enum ToolsError {
+    /// An error returned by the underlying Miden client.
+    Client(ClientError),
+    /// No note matched the requested filter.
+    NoMatchingNotes,
+    /// The same storage slot index was assigned more than once.
+    DuplicateStorageSlot(u8),
+    /// The operation did not complete before its timeout elapsed.
+    Timeout,
+    /// The account is not a fungible faucet.
+    NotAFaucet(AccountId),
+    /// A transaction failed to execute; `error_chain` holds the messages of the underlying
+    /// errors, down to the VM failure.
+    ExecutionFailed {
+        error: ClientError,
+        error_chain: Vec<String>,
+    },
+    /// The note script root differs from the expected digest.
+    NoteScriptMismatch { expected: Word, actual: Word },
+    /// A multi-output request was built without any outputs.
+    NoOutputs,
+    /// The total amount of a fungible asset exceeds the maximum representable amount.
+    AmountOverflow(AccountId),
+    /// A note could not be built.
+    Note(NoteError),
+    /// Serialized data could not be decoded.
+    Deserialization(DeserializationError),
+    /// More auth keys were requested than the protocol supports for one account.
+    UnsupportedAuthKeys { requested: usize, max: usize },
+    /// The balance matrix does not match the requested number of accounts and faucets.
+    InvalidBalanceMatrix { accounts: usize, faucets: usize },
+    /// The inclusion proof does not prove the given note.
+    InvalidInclusionProof(NoteId),
+    /// The requested amounts exceed the amount available.
+    InsufficientAmount { available: u64, requested: u64 },
+    /// The note does not carry exactly one fungible asset.
+    NotSingleFungibleAsset(NoteId),
+    /// The requested code mutability is not supported for the account type.
+    UnsupportedCodeMutability(AccountType),
+    /// A faucet metadata field is invalid.
+    InvalidFaucetMetadata { field: &'static str, reason: String },
+    /// The keystore could not be read.
+    KeyStore(KeyStoreError),
+    /// The account cannot satisfy the note's inputs or script.
+    NoteNotConsumable(NoteId),
+    /// A file could not be read.
+    Io(std::io::Error),
+    /// A configuration file is not valid TOML.
+    InvalidConfig(String),
+    /// A swap must request at least one asset.
+    NoRequestedAssets,
+    /// A swap requests assets of the faucet with this ID prefix more than once.
+    DuplicateRequestedFaucet(AccountIdPrefix),
+    /// The account code cannot be updated.
+    ImmutableAccountCode(AccountId),
+    /// The balances did not reach their expected minimum in time, as
+    /// `(account_id, faucet_id, min_balance)`.
+    BalancesNotReached(Vec<(AccountId, AccountId, u64)>),
+    /// More than one faucet was requested with the same token symbol.
+    DuplicateFaucetSymbol(String),
+    /// A transfer of zero tokens was requested.
+    ZeroAmount,
+    /// The target account is neither tracked by the client nor known to the node.
+    TargetNotFound(AccountId),
+    /// A script failed to assemble or was given invalid inputs.
+    InvalidScript(String),
+    /// A program failed in the VM.
+    Execution(ExecutionError),
+    /// The same non-fungible asset was given more than once.
+    DuplicateNonFungibleAsset(NonFungibleAsset),
+    /// The network has no canonical RPC endpoint.
+    NoCanonicalEndpoint(Network),
+    /// A transaction could not be proven.
+    Proving(TransactionProverError),
+    /// A transaction proof did not verify.
+    Verification(TransactionVerifierError),
+    /// An account balance changed by a different amount than expected.
+    UnexpectedDelta {
+        faucet_id: AccountId,
+        expected: i64,
+        actual: i64,
+    },
+    /// The note recipient digest differs from the expected one.
+    RecipientMismatch { expected: Word, actual: Word },
+    /// SQLite's integrity check reported problems with a store.
+    CorruptStore { path: String, problems: Vec<String> },
+    /// Notes were left unconsumed or uncommitted at the end of a flow.
+    DanglingNotes {
+        unconsumed: Vec<NoteId>,
+        uncommitted: Vec<NoteId>,
+    },
+    /// The note record lacks the metadata needed to rebuild the full note.
+    IncompleteNoteRecord(NoteId),
+    /// The built account's code commitment differs from the expected one.
+    CodeCommitmentMismatch { expected: Word, actual: Word },
+    /// A transaction created a note whose full details are not part of its output.
+    PartialOutputNote(NoteId),
+    /// A store to merge was created with a different schema than the target store.
+    StoreSchemaMismatch { source: String },
+    /// A store to merge holds a different version of a row the target store already has.
+    StoreMergeConflict { source: String, table: String },
+    /// Every serial number drawn for a note gave the ID of an existing note.
+    NoteIdCollision(NoteId),
+    /// More faucets were requested than there are symbols for.
+    TooManyFaucets { requested: usize, max: usize },
+    /// The account code does not contain a procedure the operation calls.
+    MissingProcedure { account_id: AccountId, root: Word },
+}
+
+impl fmt::Display for ToolsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToolsError::Client(err) => write!(f, "client error: {err}"),
+            ToolsError::NoMatchingNotes => write!(f, "no note matched the requested filter"),
+            ToolsError::DuplicateStorageSlot(index) => {
+                write!(f, "storage slot {index} was assigned more than once")
+            }
+            ToolsError::Timeout => write!(f, "operation timed out"),
+            ToolsError::NotAFaucet(id) => write!(f, "account {id} is not a fungible faucet"),
+            ToolsError::ExecutionFailed { error, error_chain } => {
+                write!(f, "transaction execution failed: {error}")?;
+                for line in error_chain {
+                    write!(f, "\n  caused by: {line}")?;
+                }
+                Ok(())
+            }
+            ToolsError::NoteScriptMismatch { expected, actual } => write!(
+                f,
+                "note script root {} does not match expected {}",
+                actual.to_hex(),
+                expected.to_hex()
+            ),
+            ToolsError::NoOutputs => write!(f, "at least one output note is required"),
+            ToolsError::AmountOverflow(faucet_id) => {
+                write!(f, "total amount of asset {faucet_id} is not representable")
+            }
+            ToolsError::Note(err) => write!(f, "note error: {err}"),
+            ToolsError::Deserialization(err) => write!(f, "deserialization error: {err}"),
+            ToolsError::UnsupportedAuthKeys { requested, max } => write!(
+                f,
+                "{requested} auth keys requested, but an account supports between 1 and {max}"
+            ),
+            ToolsError::InvalidBalanceMatrix { accounts, faucets } => write!(
+                f,
+                "balance matrix must have {accounts} rows of {faucets} entries"
+            ),
+            ToolsError::InvalidInclusionProof(note_id) => {
+                write!(
+                    f,
+                    "inclusion proof does not match note {}",
+                    note_id.to_hex()
+                )
+            }
+            ToolsError::InsufficientAmount {
+                available,
+                requested,
+            } => write!(f, "requested {requested} but only {available} is available"),
+            ToolsError::NotSingleFungibleAsset(note_id) => write!(
+                f,
+                "note {} does not carry exactly one fungible asset",
+                note_id.to_hex()
+            ),
+            ToolsError::UnsupportedCodeMutability(account_type) => {
+                write!(f, "{account_type:?} accounts cannot have updatable code")
+            }
+            ToolsError::InvalidFaucetMetadata { field, reason } => {
+                write!(f, "invalid faucet {field}: {reason}")
+            }
+            ToolsError::KeyStore(err) => write!(f, "keystore error: {err}"),
+            ToolsError::NoteNotConsumable(note_id) => write!(
+                f,
+                "note {} cannot be consumed by the account",
+                note_id.to_hex()
+            ),
+            ToolsError::Io(err) => write!(f, "io error: {err}"),
+            ToolsError::InvalidConfig(reason) => write!(f, "invalid config: {reason}"),
+            ToolsError::NoRequestedAssets => write!(f, "a swap must request at least one asset"),
+            ToolsError::DuplicateRequestedFaucet(faucet_id_prefix) => write!(
+                f,
+                "assets of faucet {} are requested more than once",
+                faucet_id_prefix.to_hex()
+            ),
+            ToolsError::ImmutableAccountCode(id) => {
+                write!(f, "account {id} does not have updatable code")
+            }
+            ToolsError::BalancesNotReached(unmet) => {
+                write!(f, "{} balances not reached in time", unmet.len())?;
+                for (account_id, faucet_id, min_balance) in unmet {
+                    write!(
+                        f,
+                        "\n  account {account_id} holds less than {min_balance} of {faucet_id}"
+                    )?;
+                }
+                Ok(())
+            }
+            ToolsError::DuplicateFaucetSymbol(symbol) => {
+                write!(f, "faucet symbol {symbol} is requested more than once")
+            }
+            ToolsError::ZeroAmount => write!(f, "the amount must be greater than zero"),
+            ToolsError::TargetNotFound(id) => write!(f, "target account {id} does not exist"),
+            ToolsError::InvalidScript(reason) => write!(f, "invalid script: {reason}"),
+            ToolsError::Execution(err) => write!(f, "execution error: {err}"),
+            ToolsError::DuplicateNonFungibleAsset(asset) => write!(
+                f,
+                "non-fungible asset {} is given more than once",
+                Word::from(*asset).to_hex()
+            ),
+            ToolsError::NoCanonicalEndpoint(network) => {
+                write!(f, "{network:?} has no canonical endpoint")
+            }
+            ToolsError::Proving(err) => write!(f, "proving error: {err}"),
+            ToolsError::Verification(err) => write!(f, "proof verification failed: {err}"),
+            ToolsError::UnexpectedDelta {
+                faucet_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "balance of {faucet_id} changed by {actual}, expected {expected}"
+            ),
+            ToolsError::RecipientMismatch { expected, actual } => write!(
+                f,
+                "note recipient {} does not match expected {}",
+                actual.to_hex(),
+                expected.to_hex()
+            ),
+            ToolsError::CorruptStore { path, problems } => {
+                write!(f, "store {path} is corrupt: {}", problems.join("; "))
+            }
+            ToolsError::DanglingNotes {
+                unconsumed,
+                uncommitted,
+            } => write!(
+                f,
+                "{} committed notes left unconsumed and {} output notes left uncommitted",
+                unconsumed.len(),
+                uncommitted.len()
+            ),
+            ToolsError::IncompleteNoteRecord(note_id) => write!(
+                f,
+                "note record {} has no metadata, only its details are known",
+                note_id.to_hex()
+            ),
+            ToolsError::CodeCommitmentMismatch { expected, actual } => write!(
+                f,
+                "account code commitment {} does not match expected {}",
+                actual.to_hex(),
+                expected.to_hex()
+            ),
+            ToolsError::PartialOutputNote(note_id) => {
+                write!(f, "output note {} is not a full note", note_id.to_hex())
+            }
+            ToolsError::StoreSchemaMismatch { source } => {
+                write!(
+                    f,
+                    "store {source} has a different schema than the target store"
+                )
+            }
+            ToolsError::StoreMergeConflict { source, table } => write!(
+                f,
+                "store {source} conflicts with the target store in table {table}"
+            ),
+            ToolsError::NoteIdCollision(note_id) => {
+                write!(f, "note {} already exists", note_id.to_hex())
+            }
+            ToolsError::TooManyFaucets { requested, max } => {
+                write!(
+                    f,
+                    "{requested} faucets requested, at most {max} are supported"
+                )
+            }
+            ToolsError::MissingProcedure { account_id, root } => write!(
+                f,
+                "account {account_id} has no procedure with root {}",
+                root.to_hex()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ToolsError {}
+
+impl From<ClientError> for ToolsError {
+    fn from(err: ClientError) -> Self {
+        ToolsError::Client(err)
+    }
+}
+
+impl From<ExecutionError> for ToolsError {
+    fn from(err: ExecutionError) -> Self {
+        ToolsError::Execution(err)
+    }
+}
+
+impl From<TransactionProverError> for ToolsError {
+    fn from(err: TransactionProverError) -> Self {
+        ToolsError::Proving(err)
+    }
+}
+
+impl From<TransactionVerifierError> for ToolsError {
+    fn from(err: TransactionVerifierError) -> Self {
+        ToolsError::Verification(err)
+    }
+}
+
+impl From<NoteError> for ToolsError {
+    fn from(err: NoteError) -> Self {
+        ToolsError::Note(err)
+    }
+}
+
+impl From<DeserializationError> for ToolsError {
+    fn from(err: DeserializationError) -> Self {
+        ToolsError::Deserialization(err)
+    }
+}
+
+impl From<std::io::Error> for ToolsError {
+    fn from(err: std::io::Error) -> Self {
+        ToolsError::Io(err)
+    }
+}
+
+impl From<TransactionRequestError> for ToolsError {
+    fn from(err: TransactionRequestError) -> Self {
+        ToolsError::Client(err.into())
+    }
+}
 
 /// Helper to instantiate a `Client` for interacting with Miden.
 ///
@@ -46,29 +532,343 @@ pub async fn instantiate_client(
     endpoint: Endpoint,
     store_path: Option<&str>,
 ) -> Result<Client, ClientError> {
+    let mut config = ClientConfig::new(endpoint);
+    if let Some(store_path) = store_path {
+        config = config.with_store_path(store_path);
+    }
+    instantiate_client_with_config(config).await
+}
+
+/// Configuration for `instantiate_client_with_config`.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// The endpoint of the RPC server to connect to.
+    pub endpoint: Endpoint,
+    /// The path to the SQLite store.
+    pub store_path: String,
+    /// Seed for the client RNG. When set, account seeds, keys and note serial numbers drawn from
+    /// the client RNG are deterministic; otherwise the RNG is seeded from OS entropy.
+    pub rng_seed: Option<[u8; 32]>,
+    /// Whether the client executes transactions in debug mode. Debug mode keeps source locations
+    /// in execution errors and runs `debug` instructions, at some cost in speed. The compilation
+    /// helpers do not take a client and always assemble in debug mode, see `tools_assembler`.
+    pub debug_mode: bool,
+    /// The maximum number of RPC calls the client has in flight at once, or `None` for no limit.
+    /// Calls over the limit wait for a running one to finish, see `LimitedRpcClient`.
+    pub max_concurrent_rpcs: Option<usize>,
+}
+
+impl ClientConfig {
+    /// Creates a configuration with the same defaults as `instantiate_client`.
+    pub fn new(endpoint: Endpoint) -> Self {
+        Self {
+            endpoint,
+            store_path: "./store.sqlite3".to_string(),
+            rng_seed: None,
+            debug_mode: true,
+            max_concurrent_rpcs: None,
+        }
+    }
+
+    /// Sets the path to the SQLite store.
+    pub fn with_store_path(mut self, store_path: &str) -> Self {
+        self.store_path = store_path.to_string();
+        self
+    }
+
+    /// Seeds the client RNG, making runs reproducible.
+    pub fn with_rng_seed(mut self, rng_seed: [u8; 32]) -> Self {
+        self.rng_seed = Some(rng_seed);
+        self
+    }
+
+    /// Enables or disables debug mode; it is enabled by default.
+    pub fn with_debug_mode(mut self, debug_mode: bool) -> Self {
+        self.debug_mode = debug_mode;
+        self
+    }
+
+    /// Limits the number of concurrent RPC calls; calls are unlimited by default.
+    pub fn with_max_concurrent_rpcs(mut self, max_concurrent_rpcs: usize) -> Self {
+        self.max_concurrent_rpcs = Some(max_concurrent_rpcs);
+        self
+    }
+}
+
+/// Bounds how many calls run at the same time.
+///
+/// Each call passed to `run` holds a semaphore permit while it is awaited; once `max` calls are
+/// in flight, further calls wait for a permit. An unlimited limiter runs every call directly.
+#[derive(Debug, Clone)]
+pub struct RpcLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl RpcLimiter {
+    /// Creates a limiter allowing at most `max` concurrent calls, or any number for `None`.
+    ///
+    /// A limit of 0 would block every call forever, so it is raised to 1.
+    pub fn new(max: Option<usize>) -> Self {
+        Self {
+            semaphore: max.map(|max| Arc::new(Semaphore::new(max.max(1)))),
+        }
+    }
+
+    /// Awaits `call` once a permit is available.
+    pub async fn run<F: Future>(&self, call: F) -> F::Output {
+        let _permit = match &self.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .expect("the limiter semaphore is never closed"),
+            ),
+            None => None,
+        };
+        call.await
+    }
+}
+
+/// A `NodeRpcClient` that passes every call through an `RpcLimiter` before delegating it.
+///
+/// `instantiate_client_with_config` wraps its `TonicRpcClient` in this when
+/// `ClientConfig::max_concurrent_rpcs` is set, so that clients syncing concurrently do not hit
+/// the node's rate limits.
+pub struct LimitedRpcClient<R> {
+    inner: R,
+    limiter: RpcLimiter,
+}
+
+impl<R: NodeRpcClient> LimitedRpcClient<R> {
+    /// Wraps `inner`, allowing at most `max_concurrent` of its calls in flight at once.
+    pub fn new(inner: R, max_concurrent: usize) -> Self {
+        Self {
+            inner,
+            limiter: RpcLimiter::new(Some(max_concurrent)),
+        }
+    }
+}
+
+#[async_trait]
+impl<R: NodeRpcClient> NodeRpcClient for LimitedRpcClient<R> {
+    async fn set_genesis_commitment(&self, commitment: Word) -> Result<(), RpcError> {
+        self.limiter
+            .run(self.inner.set_genesis_commitment(commitment))
+            .await
+    }
+
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        self.limiter
+            .run(self.inner.submit_proven_transaction(proven_transaction))
+            .await
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        self.limiter
+            .run(
+                self.inner
+                    .get_block_header_by_number(block_num, include_mmr_proof),
+            )
+            .await
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        self.limiter
+            .run(self.inner.get_block_by_number(block_num))
+            .await
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        self.limiter.run(self.inner.get_notes_by_id(note_ids)).await
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        self.limiter
+            .run(self.inner.sync_state(block_num, account_ids, note_tags))
+            .await
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        self.limiter
+            .run(self.inner.get_account_details(account_id))
+            .await
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        block_to: Option<BlockNumber>,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        self.limiter
+            .run(self.inner.sync_notes(block_num, block_to, note_tags))
+            .await
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        self.limiter
+            .run(self.inner.check_nullifiers_by_prefix(prefix, block_num))
+            .await
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        self.limiter
+            .run(self.inner.check_nullifiers(nullifiers))
+            .await
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: BTreeMap<AccountId, AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        self.limiter
+            .run(
+                self.inner
+                    .get_account_proofs(account_storage_requests, known_account_codes),
+            )
+            .await
+    }
+}
+
+/// Instantiates a `Client` from a `ClientConfig`.
+///
+/// # Arguments
+///
+/// * `config` - The client configuration.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the `Client` if successful, or a `ClientError` if an error occurs.
+pub async fn instantiate_client_with_config(config: ClientConfig) -> Result<Client, ClientError> {
+    let builder = ClientBuilder::new().filesystem_keystore("./keystore");
+    build_client(builder, &config).await
+}
+
+/// Instantiates a `Client` from a `ClientConfig` that signs with the given authenticator.
+///
+/// The filesystem keystore is not used at all: every signature is requested from
+/// `authenticator`, which can be an external signer. Accounts are still read from the store at
+/// `config.store_path`, so an account added with `add_account` through any client on that store
+/// can be used here, as long as `authenticator` can sign for the account's public key.
+///
+/// # Arguments
+///
+/// * `config` - The client configuration.
+/// * `authenticator` - The authenticator producing transaction signatures.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the `Client` if successful, or a `ClientError` if an error occurs.
+pub async fn instantiate_client_with_authenticator<
+    AUTH: TransactionAuthenticator + Send + Sync + 'static,
+>(
+    config: ClientConfig,
+    authenticator: AUTH,
+) -> Result<Client<AUTH>, ClientError> {
+    let builder = ClientBuilder::new().authenticator(Arc::new(authenticator));
+    build_client(builder, &config).await
+}
+
+async fn build_client<AUTH: TransactionAuthenticator + Send + Sync + 'static>(
+    builder: ClientBuilder<AUTH>,
+    config: &ClientConfig,
+) -> Result<Client<AUTH>, ClientError> {
     let timeout_ms = 10_000;
-    let rpc_api = Arc::new(TonicRpcClient::new(&endpoint, timeout_ms));
-
-    let client = ClientBuilder::new()
-        .rpc(rpc_api.clone())
-        .filesystem_keystore("./keystore")
-        .sqlite_store(store_path.unwrap_or("./store.sqlite3"))
-        .in_debug_mode(DebugMode::Enabled)
-        .build()
-        .await?;
+    let tonic_client = TonicRpcClient::new(&config.endpoint, timeout_ms);
+    let rpc_api: Arc<dyn NodeRpcClient + Send> = match config.max_concurrent_rpcs {
+        Some(max_concurrent) => Arc::new(LimitedRpcClient::new(tonic_client, max_concurrent)),
+        None => Arc::new(tonic_client),
+    };
+
+    let mut builder = builder
+        .rpc(rpc_api)
+        .sqlite_store(&config.store_path)
+        .in_debug_mode(if config.debug_mode {
+            DebugMode::Enabled
+        } else {
+            DebugMode::Disabled
+        });
+
+    if let Some(rng_seed) = config.rng_seed {
+        let coin_seed: [u64; 4] = ChaCha20Rng::from_seed(rng_seed).random();
+        builder = builder.rng(Box::new(RpoRandomCoin::new(
+            coin_seed.map(Felt::new).into(),
+        )));
+    }
+
+    let client = builder.build().await?;
 
     Ok(client)
 }
 
+/// Helper to instantiate a `Client` that keeps all of its state in memory.
+///
+/// The store is an SQLite database opened as a uniquely named, shared-cache in-memory database,
+/// so it needs the `sqlite` feature of `miden-client` (enabled by this crate) but never creates
+/// a file. Keys are held by an `InMemoryKeyStore`, which is also the client's authenticator. Each
+/// call gets its own database, so clients created by parallel tests do not collide.
+///
+/// # Arguments
+///
+/// * `endpoint` - The endpoint of the RPC server to connect to.
+///
+/// # Returns
+///
+/// Returns the `Client` together with the keystore to pass to the account creation helpers.
+pub async fn instantiate_client_in_memory(
+    endpoint: Endpoint,
+) -> Result<(Client<InMemoryKeyStore>, InMemoryKeyStore), ClientError> {
+    let keystore = InMemoryKeyStore::new();
+    let store_uri = format!(
+        "file:miden-client-tools-{:016x}?mode=memory&cache=shared",
+        rand::rng().random::<u64>()
+    );
+
+    let config = ClientConfig::new(endpoint).with_store_path(&store_uri);
+    let client = instantiate_client_with_authenticator(config, keystore.clone()).await?;
+
+    Ok((client, keystore))
+}
+
 /// Deletes the keystore and store files.
 ///
 /// # Arguments
 ///
 /// * `store_path` - An optional path to the SQLite store that should be deleted. Defaults to `./store.sqlite3` if not provided.
 ///
-/// This function removes all files from the keystore and deletes the SQLite store file, if they exist.
+/// This function removes the `./keystore` directory and deletes the SQLite store file, if they
+/// exist. See `delete_env` for clients using another keystore directory.
 pub async fn delete_keystore_and_store(store_path: Option<&str>) {
-    let store_path = store_path.unwrap_or("./store.sqlite3");
+    delete_env(store_path.unwrap_or("./store.sqlite3"), "./keystore").await;
+}
+
+/// Deletes a store file and a keystore directory.
+///
+/// Failures are reported but do not stop the cleanup, so this can run at the end of a test
+/// whatever state it left behind.
+///
+/// # Arguments
+///
+/// * `store_path` - The path to the SQLite store to delete.
+/// * `keystore_path` - The keystore directory to delete, with the keys it holds.
+pub async fn delete_env(store_path: &str, keystore_path: &str) {
     if tokio::fs::metadata(store_path).await.is_ok() {
         if let Err(e) = tokio::fs::remove_file(store_path).await {
             eprintln!("failed to remove {}: {}", store_path, e);
@@ -79,8 +879,7 @@ pub async fn delete_keystore_and_store(store_path: Option<&str>) {
         println!("store not found: {}", store_path);
     }
 
-    let keystore_dir = "./keystore";
-    match tokio::fs::read_dir(keystore_dir).await {
+    match tokio::fs::read_dir(keystore_path).await {
         Ok(mut dir) => {
             while let Ok(Some(entry)) = dir.next_entry().await {
                 let file_path = entry.path();
@@ -90,8 +889,11 @@ pub async fn delete_keystore_and_store(store_path: Option<&str>) {
                     println!("removed file: {}", file_path.display());
                 }
             }
+            if let Err(e) = tokio::fs::remove_dir(keystore_path).await {
+                eprintln!("failed to remove directory {}: {}", keystore_path, e);
+            }
         }
-        Err(e) => eprintln!("failed to read directory {}: {}", keystore_dir, e),
+        Err(e) => eprintln!("failed to read directory {}: {}", keystore_path, e),
     }
 }
 
@@ -129,6 +931,14 @@ fn to_elements(poly: Polynomial<Felt>) -> Vec<Felt> {
     poly.coefficients.to_vec()
 }
 
+/// Returns the length of the advice stack produced for a Falcon-512 signature.
+///
+/// The stack holds the 2 challenge elements, the 512 coefficients of `h` and of `s2`, and the
+/// 1024 coefficients of their product `pi`, i.e. `2 + 512 + 512 + 1024` elements.
+pub const fn advice_stack_len() -> usize {
+    2 + N + N + 2 * N
+}
+
 /// Generates an advice stack from a signature using two polynomials `h` and `s2`.
 ///
 /// # Arguments
@@ -140,6 +950,31 @@ fn to_elements(poly: Polynomial<Felt>) -> Vec<Felt> {
 ///
 /// Returns a vector representing the advice stack.
 pub fn generate_advice_stack_from_signature(h: Polynomial<Felt>, s2: Polynomial<Felt>) -> Vec<u64> {
+    generate_advice_stack_with_challenge(h, s2, |polynomials| {
+        let digest_polynomials = Hasher::hash_elements(polynomials);
+        (digest_polynomials[0], digest_polynomials[1])
+    })
+}
+
+/// Generates an advice stack from a signature, deriving the challenge with a custom function.
+///
+/// The layout is identical to `generate_advice_stack_from_signature`: the two challenge elements
+/// followed by `h`, `s2` and `pi = h * s2`.
+///
+/// # Arguments
+///
+/// * `h` - The first polynomial representing part of the signature.
+/// * `s2` - The second polynomial representing part of the signature.
+/// * `challenge_fn` - Derives the challenge point from the concatenated polynomials.
+///
+/// # Returns
+///
+/// Returns a vector representing the advice stack.
+pub fn generate_advice_stack_with_challenge(
+    h: Polynomial<Felt>,
+    s2: Polynomial<Felt>,
+    challenge_fn: impl Fn(&[Felt]) -> (Felt, Felt),
+) -> Vec<u64> {
     let pi = mul_modulo_p(h.clone(), s2.clone());
 
     // lay the polynomials in order h then s2 then pi = h * s2
@@ -148,8 +983,7 @@ pub fn generate_advice_stack_from_signature(h: Polynomial<Felt>, s2: Polynomial<
     polynomials.extend(pi.iter().map(|a| Felt::new(*a)));
 
     // get the challenge point and push it to the advice stack
-    let digest_polynomials = Hasher::hash_elements(&polynomials);
-    let challenge = (digest_polynomials[0], digest_polynomials[1]);
+    let challenge = challenge_fn(&polynomials);
     let mut advice_stack = vec![challenge.0.as_int(), challenge.1.as_int()];
 
     // push the polynomials to the advice stack
@@ -159,6 +993,15 @@ pub fn generate_advice_stack_from_signature(h: Polynomial<Felt>, s2: Polynomial<
     advice_stack
 }
 
+/// Returns the assembler used by the compilation helpers in this crate.
+///
+/// It is the transaction kernel assembler in debug mode, so code compiled with it links against
+/// the same kernel and produces the same digests as `create_library` and `create_tx_script`.
+/// Debug mode keeps source locations and `debug` instructions in the compiled code.
+pub fn tools_assembler() -> Assembler {
+    TransactionKernel::assembler().with_debug_mode(true)
+}
+
 /// Creates a Miden library from the provided account code and library path.
 ///
 /// # Arguments
@@ -173,7 +1016,7 @@ pub fn create_library(
     account_code: String,
     library_path: &str,
 ) -> Result<miden_assembly::Library, Box<dyn std::error::Error>> {
-    let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
+    let assembler: Assembler = tools_assembler();
     let source_manager = Arc::new(DefaultSourceManager::default());
     let module = Module::parser(ModuleKind::Library).parse_str(
         LibraryPath::new(library_path)?,
@@ -184,379 +1027,5259 @@ pub fn create_library(
     Ok(library)
 }
 
-/// Creates a basic account with a random key and adds it to the client.
+/// A source span highlighted by an `AssemblyDiagnostic`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticLabel {
+    /// The byte offset of the span in the source.
+    pub offset: usize,
+    /// The length of the span in bytes.
+    pub len: usize,
+    /// The 1-based line of the span start, if the diagnostic carries its source.
+    pub line: Option<usize>,
+    /// The 1-based column of the span start, if the diagnostic carries its source.
+    pub column: Option<usize>,
+    /// The text attached to the span.
+    pub label: Option<String>,
+}
+
+/// A structured assembler diagnostic, as returned by `create_library_diagnostic`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblyDiagnostic {
+    /// The error message.
+    pub message: String,
+    /// A hint on how to fix the error.
+    pub help: Option<String>,
+    /// The source spans the error points at.
+    pub labels: Vec<DiagnosticLabel>,
+}
+
+/// Creates a Miden library like `create_library`, keeping the assembler's diagnostics on failure.
+///
+/// Where `create_library` boxes the error, this returns one `AssemblyDiagnostic` for the error
+/// and one for each related diagnostic the assembler attached, with line and column of every
+/// labelled span. This is what editors and linters wrapping the crate need to underline the
+/// offending code.
 ///
 /// # Arguments
 ///
-/// * `client` - The Miden client to interact with.
-/// * `keystore` - The keystore to store the account's secret key.
+/// * `account_code` - The account code in MASM format.
+/// * `library_path` - The path where the library is located.
 ///
 /// # Returns
 ///
-/// Returns a tuple containing the created `Account` and the associated `SecretKey`.
-pub async fn create_basic_account(
-    client: &mut Client,
-    keystore: FilesystemKeyStore<StdRng>,
-) -> Result<(miden_client::account::Account, SecretKey), ClientError> {
-    let mut init_seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
+/// Returns the resulting `Library`, or the diagnostics describing why it cannot be created.
+pub fn create_library_diagnostic(
+    account_code: String,
+    library_path: &str,
+) -> Result<Library, Vec<AssemblyDiagnostic>> {
+    let library_path = LibraryPath::new(library_path).map_err(|err| {
+        vec![AssemblyDiagnostic {
+            message: err.to_string(),
+            help: None,
+            labels: Vec::new(),
+        }]
+    })?;
 
-    let key_pair = SecretKey::with_rng(client.rng());
-    let builder = AccountBuilder::new(init_seed)
-        // .anchor((&anchor_block).try_into().unwrap())
-        .account_type(AccountType::RegularAccountUpdatableCode)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key().clone()))
-        .with_component(BasicWallet);
+    let to_diagnostics = |report: Report| {
+        let mut diagnostics = Vec::new();
+        collect_diagnostics(&*report, None, &mut diagnostics);
+        diagnostics
+    };
 
-    let (account, seed) = builder.build().unwrap();
-    client.add_account(&account, Some(seed), false).await?;
-    keystore
-        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
-        .unwrap();
+    let source_manager = Arc::new(DefaultSourceManager::default());
+    let module = Module::parser(ModuleKind::Library)
+        .parse_str(library_path, account_code, &source_manager)
+        .map_err(to_diagnostics)?;
+    tools_assembler()
+        .assemble_library([module])
+        .map_err(to_diagnostics)
+}
 
-    Ok((account, key_pair))
+/// Appends `diagnostic` and its related diagnostics to `out`, reading label positions from the
+/// diagnostic's source code or, failing that, from its parent's.
+fn collect_diagnostics<'a>(
+    diagnostic: &'a dyn Diagnostic,
+    parent_source: Option<&'a dyn SourceCode>,
+    out: &mut Vec<AssemblyDiagnostic>,
+) {
+    let source_code = diagnostic.source_code().or(parent_source);
+    let labels = diagnostic
+        .labels()
+        .into_iter()
+        .flatten()
+        .map(|label| diagnostic_label(source_code, &label))
+        .collect();
+
+    out.push(AssemblyDiagnostic {
+        message: diagnostic.to_string(),
+        help: diagnostic.help().map(|help| help.to_string()),
+        labels,
+    });
+
+    for related in diagnostic.related().into_iter().flatten() {
+        collect_diagnostics(related, source_code, out);
+    }
 }
 
-/// Creates a basic faucet account with a fungible asset.
-///
-/// # Arguments
-///
-/// * `client` - The Miden client to interact with.
-/// * `keystore` - The keystore to store the faucet's secret key.
+fn diagnostic_label(source_code: Option<&dyn SourceCode>, label: &LabeledSpan) -> DiagnosticLabel {
+    let position = source_code
+        .and_then(|source_code| source_code.read_span(label.inner(), 0, 0).ok())
+        .map(|contents| (contents.line() + 1, contents.column() + 1));
+
+    DiagnosticLabel {
+        offset: label.offset(),
+        len: label.len(),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+        label: label.label().map(str::to_string),
+    }
+}
+
+/// Compiles a note script, optionally linking it against an account library.
+fn compile_note_script(
+    note_code: &str,
+    library: Option<&Library>,
+) -> Result<NoteScript, ToolsError> {
+    let assembler = match library {
+        Some(library) => tools_assembler().with_dynamic_library(library),
+        None => Ok(tools_assembler()),
+    };
+    let program = assembler
+        .and_then(|assembler| assembler.assemble_program(note_code))
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+    Ok(NoteScript::new(program))
+}
+
+/// Compiles a transaction script, optionally linking it against an account library.
+fn compile_tx_script(
+    script_code: &str,
+    library: Option<&Library>,
+) -> Result<TransactionScript, ToolsError> {
+    let builder = match library {
+        Some(library) => ScriptBuilder::new(true).with_dynamically_linked_library(library),
+        None => Ok(ScriptBuilder::new(true)),
+    };
+    builder
+        .and_then(|builder| builder.compile_tx_script(script_code))
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))
+}
+
+/// Estimates the number of VM cycles a script takes, without a client or a node.
+///
+/// The script is assembled as a program, with `library` dynamically linked like in
+/// `create_tx_script`, and run in a bare VM whose host only knows the standard library, the
+/// Miden library and `library`. There is no transaction kernel, so scripts that reach kernel
+/// procedures, e.g. through `miden::account` like the bundled counter does, fail with
+/// `ToolsError::Execution`; the estimate is meant for self-contained computations. Use
+/// `estimate_tx_script_cycles` for scripts that need the kernel.
+///
+/// # Arguments
+///
+/// * `script_code` - The script in MASM format.
+/// * `library` - An optional library the script calls into.
+/// * `inputs` - The initial operand stack, with the first element on top.
+///
+/// # Returns
+///
+/// Returns the number of cycles of the execution, or `ToolsError::InvalidScript` if the script
+/// does not assemble or the inputs are invalid.
+pub fn estimate_script_cycles(
+    script_code: &str,
+    library: Option<&Library>,
+    inputs: &[Felt],
+) -> Result<u64, ToolsError> {
+    let mut assembler = tools_assembler();
+    if let Some(library) = library {
+        assembler = assembler
+            .with_dynamic_library(library)
+            .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+    }
+    let source_manager = assembler.source_manager();
+    let program = assembler
+        .assemble_program(script_code)
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+
+    let stack_inputs = StackInputs::new(inputs.to_vec())
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+
+    let mut host = DefaultHost::default();
+    host.load_library(StdLibrary::default().mast_forest().clone())?;
+    host.load_library(MidenLib::default().mast_forest().clone())?;
+    if let Some(library) = library {
+        host.load_library(library.mast_forest().clone())?;
+    }
+
+    let trace = execute_program(
+        &program,
+        stack_inputs,
+        AdviceInputs::default(),
+        &mut host,
+        ExecutionOptions::default(),
+        source_manager,
+    )?;
+    Ok(trace.trace_len_summary().main_trace_len() as u64)
+}
+
+/// Estimates the number of VM cycles a transaction script takes when run against an account.
+///
+/// Unlike `estimate_script_cycles`, the script runs inside the transaction kernel: the
+/// transaction is executed locally by the client, against the account's current state, and
+/// neither proven nor submitted. Only the cycles spent in the script itself are counted, not the
+/// prologue, epilogue or auth procedure.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to execute the transaction.
+/// * `account_id` - The account executing the script.
+/// * `tx_script` - The compiled transaction script.
+///
+/// # Returns
+///
+/// Returns the number of cycles spent in the script, or a `ClientError` if the transaction
+/// fails to execute.
+pub async fn estimate_tx_script_cycles<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    tx_script: TransactionScript,
+) -> Result<u64, ClientError> {
+    let tx_req = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()?;
+    let tx_result = client.new_transaction(account_id, tx_req).await?;
+    Ok(tx_result
+        .executed_transaction()
+        .measurements()
+        .tx_script_processing as u64)
+}
+
+/// Compiles the MASM files bundled with this crate.
+///
+/// `counter.masm` is compiled as a library under `external_contract::counter_contract`, and
+/// `increment_script.masm` and `increment_note.masm` are compiled against it, the same way
+/// `increment_counter` and the tests use them. This is a quick smoke test that the embedded
+/// assets still compile with the current assembler.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if every file compiles, otherwise the path and error message of each file
+/// that failed.
+pub fn verify_bundled_masm() -> Result<(), Vec<(String, String)>> {
+    const COUNTER: &str = "masm/accounts/counter.masm";
+    const INCREMENT_SCRIPT: &str = "masm/scripts/increment_script.masm";
+    const INCREMENT_NOTE: &str = "masm/notes/increment_note.masm";
+
+    let mut errors = Vec::new();
+
+    let library = match create_library(
+        include_str!("../masm/accounts/counter.masm").to_string(),
+        "external_contract::counter_contract",
+    ) {
+        Ok(library) => library,
+        Err(err) => {
+            errors.push((COUNTER.to_string(), err.to_string()));
+            for path in [INCREMENT_SCRIPT, INCREMENT_NOTE] {
+                errors.push((path.to_string(), format!("not compiled: {COUNTER} failed")));
+            }
+            return Err(errors);
+        }
+    };
+
+    let script = ScriptBuilder::new(true)
+        .with_dynamically_linked_library(&library)
+        .and_then(|builder| {
+            builder.compile_tx_script(include_str!("../masm/scripts/increment_script.masm"))
+        });
+    if let Err(err) = script {
+        errors.push((INCREMENT_SCRIPT.to_string(), err.to_string()));
+    }
+
+    let note = tools_assembler()
+        .with_dynamic_library(&library)
+        .and_then(|assembler| {
+            assembler.assemble_program(include_str!("../masm/notes/increment_note.masm"))
+        });
+    if let Err(err) = note {
+        errors.push((INCREMENT_NOTE.to_string(), err.to_string()));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// A thread-safe cache of compiled note and transaction scripts.
+///
+/// Entries are keyed by the script source and the digest of the library it is linked against, so
+/// the same cache can be shared across accounts and tasks. Pass it to
+/// `create_public_note_with_options`, or call `LibraryCache::tx_script`, to avoid recompiling the
+/// same code on every call.
+#[derive(Debug, Default)]
+pub struct LibraryCache {
+    note_scripts: Mutex<HashMap<(String, Option<Word>), NoteScript>>,
+    tx_scripts: Mutex<HashMap<(String, Option<Word>), TransactionScript>>,
+}
+
+impl LibraryCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the compiled note script for `note_code`, compiling it on the first request.
+    ///
+    /// The script is compiled without holding the cache lock, so two concurrent first requests
+    /// may both compile it; scripts that fail to compile are not cached.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `NoteScript`, or `ToolsError::InvalidScript` if the code does not compile.
+    pub fn note_script(
+        &self,
+        note_code: &str,
+        library: Option<&Library>,
+    ) -> Result<NoteScript, ToolsError> {
+        let key = (note_code.to_string(), library.map(|lib| *lib.digest()));
+        if let Some(script) = self.note_scripts.lock().unwrap().get(&key) {
+            return Ok(script.clone());
+        }
+
+        let script = compile_note_script(note_code, library)?;
+        let mut note_scripts = self.note_scripts.lock().unwrap();
+        Ok(note_scripts.entry(key).or_insert(script).clone())
+    }
+
+    /// Returns the compiled transaction script for `script_code`, compiling it on the first
+    /// request.
+    ///
+    /// Like `note_script`, the script is compiled without holding the cache lock.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `TransactionScript`, or `ToolsError::InvalidScript` if the code does not
+    /// compile.
+    pub fn tx_script(
+        &self,
+        script_code: &str,
+        library: Option<&Library>,
+    ) -> Result<TransactionScript, ToolsError> {
+        let key = (script_code.to_string(), library.map(|lib| *lib.digest()));
+        if let Some(script) = self.tx_scripts.lock().unwrap().get(&key) {
+            return Ok(script.clone());
+        }
+
+        let script = compile_tx_script(script_code, library)?;
+        let mut tx_scripts = self.tx_scripts.lock().unwrap();
+        Ok(tx_scripts.entry(key).or_insert(script).clone())
+    }
+
+    /// Returns the number of cached scripts.
+    pub fn len(&self) -> usize {
+        self.note_scripts.lock().unwrap().len() + self.tx_scripts.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no script has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A thread-safe registry of the libraries of accounts created by `create_custom_account`.
+///
+/// Scripts and notes calling an account's procedures are compiled against its library; keeping
+/// it keyed by account ID avoids recompiling the account code every time.
+#[derive(Debug, Default)]
+pub struct AccountLibraries {
+    libraries: Mutex<HashMap<AccountId, Library>>,
+}
+
+impl AccountLibraries {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the library of an account, replacing any previous one.
+    pub fn insert(&self, account_id: AccountId, library: Library) {
+        self.libraries.lock().unwrap().insert(account_id, library);
+    }
+
+    /// Returns the library registered for `account_id`, if any.
+    pub fn library_for_account(&self, account_id: AccountId) -> Option<Library> {
+        self.libraries.lock().unwrap().get(&account_id).cloned()
+    }
+
+    /// Returns the number of registered libraries.
+    pub fn len(&self) -> usize {
+        self.libraries.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no library has been registered yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Builds a list of storage slots initialized with the given values.
+///
+/// Slots that are not listed are filled with `StorageSlot::empty_value()`, so the resulting
+/// vector is as long as the highest index plus one.
+///
+/// # Arguments
+///
+/// * `values` - Pairs of slot index and the word to store in that slot.
+///
+/// # Returns
+///
+/// Returns the storage slots, or `ToolsError::DuplicateStorageSlot` if an index appears twice.
+pub fn storage_slots_from(values: &[(u8, Word)]) -> Result<Vec<StorageSlot>, ToolsError> {
+    let len = values
+        .iter()
+        .map(|(index, _)| *index as usize + 1)
+        .max()
+        .unwrap_or(0);
+    let mut slots: Vec<Option<Word>> = vec![None; len];
+
+    for (index, value) in values {
+        let slot = &mut slots[*index as usize];
+        if slot.is_some() {
+            return Err(ToolsError::DuplicateStorageSlot(*index));
+        }
+        *slot = Some(*value);
+    }
+
+    Ok(slots
+        .into_iter()
+        .map(|value| value.map_or_else(StorageSlot::empty_value, StorageSlot::Value))
+        .collect())
+}
+
+/// Compiles an account component from MASM code with the given initial storage.
+///
+/// # Arguments
+///
+/// * `account_code` - The account code in MASM format.
+/// * `storage_slots` - The initial storage slots, e.g. built with `storage_slots_from`.
+///
+/// # Returns
+///
+/// Returns the compiled `AccountComponent`, supporting all account types.
+pub fn compile_account_component(
+    account_code: String,
+    storage_slots: Vec<StorageSlot>,
+) -> Result<AccountComponent, AccountError> {
+    Ok(
+        AccountComponent::compile(account_code, tools_assembler(), storage_slots)?
+            .with_supports_all_types(),
+    )
+}
+
+/// Creates a public account from custom MASM code and registers its library.
+///
+/// The code is compiled once as a library under `library_path`, for scripts calling the account,
+/// and once as an account component. The account combines that component with `BasicWallet` and
+/// `NoAuth`, like the accounts of the counter examples.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_code` - The account code in MASM format.
+/// * `library_path` - The path the account's procedures are imported from, e.g.
+///   `external_contract::counter_contract`.
+/// * `storage_slots` - The initial storage slots of the component.
+/// * `libraries` - The registry the account's library is added to.
+///
+/// # Returns
+///
+/// Returns the created `Account` and its library, or `ToolsError::InvalidScript` if the code
+/// does not compile.
+pub async fn create_custom_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_code: String,
+    library_path: &str,
+    storage_slots: Vec<StorageSlot>,
+    libraries: &AccountLibraries,
+) -> Result<(Account, Library), ToolsError> {
+    build_custom_account(
+        client,
+        account_code,
+        library_path,
+        storage_slots,
+        libraries,
+        None,
+    )
+    .await
+}
+
+/// Creates a custom account like `create_custom_account`, checking its code commitment.
+///
+/// The commitment covers the procedures of every component, so it only matches if the account
+/// code compiles to exactly the same MASM as the externally deployed contract (and is combined
+/// with the same `BasicWallet` and `NoAuth` components). The check happens before the account is
+/// added to the client or registered.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_code` - The account code in MASM format.
+/// * `library_path` - The path the account's procedures are imported from.
+/// * `storage_slots` - The initial storage slots of the component.
+/// * `libraries` - The registry the account's library is added to.
+/// * `expected_code_commitment` - The code commitment the account must have.
+///
+/// # Returns
+///
+/// Returns the created `Account` and its library, or `ToolsError::CodeCommitmentMismatch` if the
+/// compiled code differs.
+pub async fn create_custom_account_checked<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_code: String,
+    library_path: &str,
+    storage_slots: Vec<StorageSlot>,
+    libraries: &AccountLibraries,
+    expected_code_commitment: Word,
+) -> Result<(Account, Library), ToolsError> {
+    build_custom_account(
+        client,
+        account_code,
+        library_path,
+        storage_slots,
+        libraries,
+        Some(expected_code_commitment),
+    )
+    .await
+}
+
+/// Builds, adds and registers a custom account, optionally checking its code commitment first.
+async fn build_custom_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_code: String,
+    library_path: &str,
+    storage_slots: Vec<StorageSlot>,
+    libraries: &AccountLibraries,
+    expected_code_commitment: Option<Word>,
+) -> Result<(Account, Library), ToolsError> {
+    let library = create_library(account_code.clone(), library_path)
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+    let component = compile_account_component(account_code, storage_slots)
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let builder = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_component(component)
+        .with_component(BasicWallet)
+        .with_auth_component(NoAuth);
+
+    let (account, seed) = builder.build().map_err(ClientError::from)?;
+    if let Some(expected) = expected_code_commitment {
+        let actual = account.code().commitment();
+        if actual != expected {
+            return Err(ToolsError::CodeCommitmentMismatch { expected, actual });
+        }
+    }
+    client.add_account(&account, Some(seed), false).await?;
+    libraries.insert(account.id(), library.clone());
+
+    Ok((account, library))
+}
+
+/// Computes the storage commitment an account holding only `component`'s storage would have.
+///
+/// The account builder lays out the slots of all components in order, so this matches the
+/// built account's storage commitment when `component` is the only component with storage,
+/// e.g. when combined with `BasicWallet` and `NoAuth`.
+///
+/// # Arguments
+///
+/// * `component` - The account component whose storage to commit to.
+///
+/// # Returns
+///
+/// Returns the storage root as a `Word`.
+pub fn component_storage_root(component: &AccountComponent) -> Word {
+    AccountStorage::new(component.storage_slots().to_vec())
+        .expect("account components never exceed the storage slot limit")
+        .commitment()
+}
+
+/// Creates a basic account with a random key and adds it to the client.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the account's secret key.
+///
+/// # Returns
+///
+/// Returns a tuple containing the created `Account` and the associated `SecretKey`.
+pub async fn create_basic_account<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+) -> Result<(miden_client::account::Account, SecretKey), ClientError> {
+    build_basic_account(client, keystore, AccountStorageMode::Public).await
+}
+
+/// Creates a basic account after syncing the client to the chain tip.
+///
+/// Accounts no longer commit to an anchor block, so nothing about the account itself depends on
+/// the block returned here: it is the block the client synced to right before creating the
+/// account, i.e. the tip of the chain at creation time. It can be used as a lower bound when
+/// looking for the account's first transaction.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the account's secret key.
+///
+/// # Returns
+///
+/// Returns the created `Account`, its `SecretKey` and the chain tip at creation time.
+pub async fn create_basic_account_anchored_latest<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+) -> Result<(Account, SecretKey, BlockNumber), ClientError> {
+    let anchor = client.sync_state().await?.block_num;
+    let (account, key_pair) = create_basic_account(client, keystore).await?;
+    Ok((account, key_pair, anchor))
+}
+
+/// Creates several basic accounts, one per requested storage mode.
+///
+/// Each account is built like in `create_basic_account`, except that its storage mode is taken
+/// from the corresponding entry of `specs`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the accounts' secret keys.
+/// * `specs` - The storage mode of each account to create.
+///
+/// # Returns
+///
+/// Returns the created accounts and their secret keys, in the order of `specs`.
+pub async fn create_accounts_mixed<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    specs: &[AccountStorageMode],
+) -> Result<Vec<(Account, SecretKey)>, ClientError> {
+    let mut accounts = Vec::with_capacity(specs.len());
+    for &storage_mode in specs {
+        accounts.push(build_basic_account(client, keystore.clone(), storage_mode).await?);
+    }
+    Ok(accounts)
+}
+
+async fn build_basic_account<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    storage_mode: AccountStorageMode,
+) -> Result<(Account, SecretKey), ClientError> {
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let key_pair = SecretKey::with_rng(client.rng());
+    let builder = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(storage_mode)
+        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key().clone()))
+        .with_component(BasicWallet);
+
+    let (account, seed) = builder.build().unwrap();
+    client.add_account(&account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
+        .unwrap();
+
+    Ok((account, key_pair))
+}
+
+/// Creates a basic faucet account with a fungible asset.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the faucet's secret key.
+///
+/// # Returns
+///
+/// Returns the created faucet `Account`.
+pub async fn create_basic_faucet<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+) -> Result<miden_client::account::Account, ClientError> {
+    let symbol = TokenSymbol::new("MID").unwrap();
+    build_faucet(client, keystore, symbol, 8, Felt::new(1_000_000)).await
+}
+
+/// Builds a public fungible faucet with the given metadata and adds it to the client.
+async fn build_faucet<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    symbol: TokenSymbol,
+    decimals: u8,
+    max_supply: Felt,
+) -> Result<Account, ClientError> {
+    let mut init_seed = [0u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+    let key_pair = SecretKey::with_rng(client.rng());
+    let builder = AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key()))
+        .with_component(BasicFungibleFaucet::new(symbol, decimals, max_supply).unwrap());
+    let (account, seed) = builder.build().unwrap();
+    client.add_account(&account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
+        .unwrap();
+    Ok(account)
+}
+
+/// Whether an account's code can be updated after deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeMutability {
+    /// The code is fixed once the account is created.
+    Immutable,
+    /// The code can be updated later.
+    Updatable,
+}
+
+/// The metadata of a fungible faucet created by `create_faucet_with_metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaucetMetadata {
+    /// The token symbol, up to 6 uppercase letters.
+    pub symbol: String,
+    /// The number of decimals of the token.
+    pub decimals: u8,
+    /// The maximum number of tokens the faucet can issue.
+    pub max_supply: u64,
+    /// Whether the faucet code can be updated.
+    ///
+    /// Fungible faucets only support `CodeMutability::Immutable`: the protocol has a single
+    /// `AccountType::FungibleFaucet` type, with no updatable-code counterpart like regular
+    /// accounts have.
+    pub code: CodeMutability,
+}
+
+impl Default for FaucetMetadata {
+    /// The metadata used by `create_basic_faucet`.
+    fn default() -> Self {
+        Self {
+            symbol: "MID".to_string(),
+            decimals: 8,
+            max_supply: 1_000_000,
+            code: CodeMutability::Immutable,
+        }
+    }
+}
+
+/// Checks faucet metadata against the protocol limits and returns the parsed token symbol.
+fn validate_faucet_metadata(metadata: &FaucetMetadata) -> Result<TokenSymbol, ToolsError> {
+    if metadata.code == CodeMutability::Updatable {
+        return Err(ToolsError::UnsupportedCodeMutability(
+            AccountType::FungibleFaucet,
+        ));
+    }
+
+    let symbol =
+        TokenSymbol::new(&metadata.symbol).map_err(|err| ToolsError::InvalidFaucetMetadata {
+            field: "symbol",
+            reason: err.to_string(),
+        })?;
+    if metadata.decimals > BasicFungibleFaucet::MAX_DECIMALS {
+        return Err(ToolsError::InvalidFaucetMetadata {
+            field: "decimals",
+            reason: format!("must be at most {}", BasicFungibleFaucet::MAX_DECIMALS),
+        });
+    }
+    if metadata.max_supply == 0 || metadata.max_supply > FungibleAsset::MAX_AMOUNT {
+        return Err(ToolsError::InvalidFaucetMetadata {
+            field: "max_supply",
+            reason: format!("must be between 1 and {}", FungibleAsset::MAX_AMOUNT),
+        });
+    }
+
+    Ok(symbol)
+}
+
+/// Creates a fungible faucet with custom metadata.
+///
+/// The metadata is fixed once the faucet exists: the `BasicFungibleFaucet` component stores
+/// `[max_supply, decimals, symbol, 0]` in a storage slot that none of its procedures write to,
+/// so changing the symbol, decimals or max supply requires deploying a new faucet.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the faucet's secret key.
+/// * `metadata` - The symbol, decimals, max supply and code mutability of the faucet.
+///
+/// # Returns
+///
+/// Returns the created faucet `Account`, or an error naming the invalid metadata field.
+pub async fn create_faucet_with_metadata<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    metadata: FaucetMetadata,
+) -> Result<Account, ToolsError> {
+    let symbol = validate_faucet_metadata(&metadata)?;
+    Ok(build_faucet(
+        client,
+        keystore,
+        symbol,
+        metadata.decimals,
+        Felt::new(metadata.max_supply),
+    )
+    .await?)
+}
+
+/// Creates a fungible faucet from a TOML file describing its metadata.
+///
+/// The file must define `symbol` (a string), `decimals` and `max_supply` (integers), e.g.
+///
+/// ```toml
+/// symbol = "MID"
+/// decimals = 8
+/// max_supply = 1000000
+/// ```
+///
+/// The values are validated like in `create_faucet_with_metadata`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the faucet's secret key.
+/// * `path` - The path to the TOML file.
+///
+/// # Returns
+///
+/// Returns the created faucet `Account`, or `ToolsError::InvalidFaucetMetadata` naming the
+/// missing or invalid field.
+pub async fn create_faucet_from_config<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    path: impl AsRef<std::path::Path>,
+) -> Result<Account, ToolsError> {
+    let contents = std::fs::read_to_string(path)?;
+    let table: toml::Table = contents
+        .parse()
+        .map_err(|err: toml::de::Error| ToolsError::InvalidConfig(err.to_string()))?;
+
+    let field = |name: &'static str| {
+        table
+            .get(name)
+            .ok_or_else(|| ToolsError::InvalidFaucetMetadata {
+                field: name,
+                reason: "missing".to_string(),
+            })
+    };
+    let invalid = |name: &'static str, reason: &str| ToolsError::InvalidFaucetMetadata {
+        field: name,
+        reason: reason.to_string(),
+    };
+
+    let symbol = field("symbol")?
+        .as_str()
+        .ok_or_else(|| invalid("symbol", "must be a string"))?
+        .to_string();
+    let decimals = field("decimals")?
+        .as_integer()
+        .and_then(|decimals| u8::try_from(decimals).ok())
+        .ok_or_else(|| invalid("decimals", "must be an integer between 0 and 255"))?;
+    let max_supply = field("max_supply")?
+        .as_integer()
+        .and_then(|max_supply| u64::try_from(max_supply).ok())
+        .ok_or_else(|| invalid("max_supply", "must be a non-negative integer"))?;
+
+    let metadata = FaucetMetadata {
+        symbol,
+        decimals,
+        max_supply,
+        code: CodeMutability::Immutable,
+    };
+    create_faucet_with_metadata(client, keystore, metadata).await
+}
+
+/// Creates several fungible faucets, each with its own symbol, decimals and max supply.
+///
+/// All specs are validated before any faucet is created, so an invalid or repeated symbol
+/// leaves the client untouched.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the faucets' secret keys.
+/// * `specs` - One `(symbol, decimals, max_supply)` triple per faucet.
+///
+/// # Returns
+///
+/// Returns the created faucets in the order of `specs`, `ToolsError::DuplicateFaucetSymbol` if
+/// a symbol appears twice, or `ToolsError::InvalidFaucetMetadata` naming the invalid field.
+pub async fn create_faucets<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    specs: &[(String, u8, u64)],
+) -> Result<Vec<Account>, ToolsError> {
+    let mut seen = HashSet::new();
+    let mut validated = Vec::with_capacity(specs.len());
+    for (symbol, decimals, max_supply) in specs {
+        if !seen.insert(symbol.as_str()) {
+            return Err(ToolsError::DuplicateFaucetSymbol(symbol.clone()));
+        }
+        let metadata = FaucetMetadata {
+            symbol: symbol.clone(),
+            decimals: *decimals,
+            max_supply: *max_supply,
+            code: CodeMutability::Immutable,
+        };
+        validated.push((validate_faucet_metadata(&metadata)?, metadata));
+    }
+
+    let mut faucets = Vec::with_capacity(specs.len());
+    for (symbol, metadata) in validated {
+        let faucet = build_faucet(
+            client,
+            keystore.clone(),
+            symbol,
+            metadata.decimals,
+            Felt::new(metadata.max_supply),
+        )
+        .await?;
+        faucets.push(faucet);
+    }
+
+    Ok(faucets)
+}
+
+/// Number of distinct symbols `setup_accounts_and_faucets` can give its faucets.
+pub const MAX_SETUP_FAUCETS: usize = 26 + 26 * 26 + 26 * 26 * 26;
+
+/// Returns the symbol `setup_accounts_and_faucets` gives its faucet at `index`.
+///
+/// The symbols are `MIDA` to `MIDZ`, then `MIDAA`, `MIDAB` and so on up to `MIDZZZ`, the longest
+/// valid token symbol. There is no symbol for an index past `MIDZZZ`.
+fn setup_faucet_symbol(index: usize) -> Option<String> {
+    if index >= MAX_SETUP_FAUCETS {
+        return None;
+    }
+
+    let mut suffix = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        suffix.push(char::from(b'A' + (n % 26) as u8));
+        n /= 26;
+    }
+    Some(format!(
+        "MID{}",
+        suffix.into_iter().rev().collect::<String>()
+    ))
+}
+
+/// Sets up a specified number of accounts and faucets, and mints tokens for each account.
+///
+/// This function creates a set of basic accounts and faucets, and mints tokens from each faucet to the accounts
+/// based on the given balance matrix. The input is checked with `plan_setup` before anything is
+/// created. Faucets are created with `create_faucets` and get distinct symbols: `MIDA` for the
+/// first, `MIDB` for the second, and so on, up to `MAX_SETUP_FAUCETS` faucets.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `keystore` - The keystore used to securely store account keys.
+/// * `num_accounts` - The number of accounts to create.
+/// * `num_faucets` - The number of faucets to create.
+/// * `balances` - A matrix where each entry represents the number of tokens to mint from a faucet to an account.
+///
+/// # Returns
+///
+/// Returns a tuple containing the created accounts and faucets as vectors,
+/// `ToolsError::InvalidBalanceMatrix` if `balances` does not fit the counts, or
+/// `ToolsError::TooManyFaucets` if more than `MAX_SETUP_FAUCETS` faucets are requested.
+pub async fn setup_accounts_and_faucets<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    num_accounts: usize,
+    num_faucets: usize,
+    balances: Vec<Vec<u64>>,
+) -> Result<(Vec<Account>, Vec<Account>), ToolsError> {
+    let plan = plan_setup(num_accounts, num_faucets, &balances)?;
+    let specs = (0..num_faucets)
+        .map(|index| setup_faucet_symbol(index).map(|symbol| (symbol, 8, 1_000_000)))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(ToolsError::TooManyFaucets {
+            requested: num_faucets,
+            max: MAX_SETUP_FAUCETS,
+        })?;
+
+    let mut accounts = Vec::with_capacity(num_accounts);
+    for i in 0..num_accounts {
+        let (account, _) = create_basic_account(client, keystore.clone()).await?;
+        tracing::info!(index = i, account_id = %account.id(), "created account");
+        accounts.push(account);
+    }
+
+    let faucets = create_faucets(client, keystore, &specs).await?;
+    for (j, faucet) in faucets.iter().enumerate() {
+        tracing::info!(index = j, faucet_id = %faucet.id(), "created faucet");
+    }
+
+    client.sync_state().await?;
+
+    for mint in plan.mints {
+        let account = &accounts[mint.account_index];
+        let faucet = &faucets[mint.faucet_index];
+        tracing::info!(
+            amount = mint.amount,
+            faucet_index = mint.faucet_index,
+            account_index = mint.account_index,
+            "minting tokens"
+        );
+
+        let fungible_asset =
+            FungibleAsset::new(faucet.id(), mint.amount).map_err(ClientError::AssetError)?;
+        let tx_req = TransactionRequestBuilder::new().build_mint_fungible_asset(
+            fungible_asset,
+            account.id(),
+            NoteType::Public,
+            client.rng(),
+        )?;
+
+        let tx_exec = client.new_transaction(faucet.id(), tx_req).await?;
+        submit_transaction_with_retry(client, tx_exec.clone(), SUBMIT_RETRIES, SUBMIT_RETRY_DELAY)
+            .await?;
+
+        let minted_note = match tx_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note.clone(),
+            output_note => return Err(ToolsError::PartialOutputNote(output_note.id())),
+        };
+
+        wait_for_note(client, &minted_note).await?;
+        client.sync_state().await?;
+
+        let consume_req = TransactionRequestBuilder::new()
+            .authenticated_input_notes([(minted_note.id(), None)])
+            .build()?;
+
+        let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+        submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+        client.sync_state().await?;
+    }
+
+    Ok((accounts, faucets))
+}
+
+/// Reads the balance of every faucet's asset for every account.
+///
+/// The result has the same shape as the `balances` matrix passed to `setup_accounts_and_faucets`,
+/// so the two can be compared directly to check that the setup minted what was requested.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `accounts` - The accounts whose balances are read, one row each.
+/// * `faucets` - The faucets issuing the assets, one column each.
+///
+/// # Returns
+///
+/// Returns the balance matrix as stored by the client.
+pub async fn balance_report<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    accounts: &[Account],
+    faucets: &[Account],
+) -> Result<Vec<Vec<u64>>, ClientError> {
+    let mut report = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let record = client
+            .get_account(account.id())
+            .await?
+            .ok_or(ClientError::AccountDataNotFound(account.id()))?;
+        let vault = record.account().vault();
+
+        let row = faucets
+            .iter()
+            .map(|faucet| vault.get_balance(faucet.id()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ClientError::AssetError)?;
+        report.push(row);
+    }
+
+    Ok(report)
+}
+
+/// Writes the balance matrix of `balance_report` as CSV.
+///
+/// The header row holds `account_id` followed by one column per faucet, named after the token
+/// symbol of the faucet's `BasicFungibleFaucet` metadata. Faucets without readable metadata are
+/// named by their hex ID instead. Each following row holds an account's hex ID and balances.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `accounts` - The accounts whose balances are written, one row each.
+/// * `faucets` - The faucets issuing the assets, one column each.
+/// * `writer` - The destination of the CSV, e.g. a file or an in-memory buffer.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the CSV is written.
+pub async fn balances_to_csv<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    accounts: &[Account],
+    faucets: &[Account],
+    mut writer: impl std::io::Write,
+) -> Result<(), ToolsError> {
+    use std::io::Write;
+
+    let report = balance_report(client, accounts, faucets).await?;
+
+    let mut header = vec!["account_id".to_string()];
+    header.extend(faucets.iter().map(|faucet| {
+        BasicFungibleFaucet::try_from(faucet.clone())
+            .ok()
+            .and_then(|metadata| metadata.symbol().to_string().ok())
+            .unwrap_or_else(|| faucet.id().to_hex())
+    }));
+    writeln!(writer, "{}", header.join(","))?;
+
+    for (account, balances) in accounts.iter().zip(report) {
+        let row: Vec<String> = std::iter::once(account.id().to_hex())
+            .chain(balances.iter().map(u64::to_string))
+            .collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// Mints tokens from a faucet to an account.
+///
+/// This function mints a specified amount of tokens from a faucet to an account, and waits for the transaction
+/// to be confirmed. It optionally executes a custom transaction script if provided.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account that will receive the tokens.
+/// * `faucet` - The faucet to mint tokens from.
+/// * `amount` - The number of tokens to mint.
+/// * `tx_script` - An optional custom transaction script to execute after the minting transaction. If `None`, no script is executed.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating whether the minting process was successful or not. If the transaction script is provided, it will also be executed
+/// after the minting process, otherwise, only the minting transaction is processed.
+pub async fn mint_from_faucet_for_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    faucet: &Account,
+    amount: u64,
+    tx_script: Option<TransactionScript>, // Make tx_script optional
+) -> Result<(), ClientError> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let asset = FungibleAsset::new(faucet.id(), amount).unwrap();
+    let mint_req = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+        .unwrap();
+
+    let mint_exec = client.new_transaction(faucet.id(), mint_req).await?;
+    submit_transaction_with_retry(
+        client,
+        mint_exec.clone(),
+        SUBMIT_RETRIES,
+        SUBMIT_RETRY_DELAY,
+    )
+    .await?;
+
+    let minted_note = match mint_exec.created_notes().get_note(0) {
+        OutputNote::Full(note) => note.clone(),
+        _ => panic!("Expected full minted note"),
+    };
+
+    let consume_req = if let Some(script) = tx_script {
+        TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(minted_note, None)])
+            .custom_script(script)
+            .build()?
+    } else {
+        TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(minted_note, None)])
+            .build()?
+    };
+
+    let consume_exec = client
+        .new_transaction(account.id(), consume_req)
+        .await
+        .unwrap();
+
+    submit_transaction_with_retry(
+        client,
+        consume_exec.clone(),
+        SUBMIT_RETRIES,
+        SUBMIT_RETRY_DELAY,
+    )
+    .await?;
+    client.sync_state().await?;
+
+    Ok(())
+}
+
+/// Creates a public note in the blockchain.
+///
+/// This function creates a public note using the provided note code, account library (if any), and other
+/// related parameters.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `note_code` - The code for the note, typically written in MASM.
+/// * `account_library` - An optional library that might be used during note creation.
+/// * `creator_account` - The account creating the note.
+/// * `assets` - The assets associated with the note (optional).
+/// * `note_inputs` - The inputs associated with the note (optional).
+///
+/// # Returns
+///
+/// Returns a `Result` containing the created `Note`, or `ToolsError::InvalidScript` if the note
+/// code does not compile.
+pub async fn create_public_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    note_code: String,
+    account_library: Option<Library>,
+    creator_account: Account,
+    assets: Option<NoteAssets>,
+    note_inputs: Option<NoteInputs>,
+) -> Result<Note, ToolsError> {
+    create_public_note_with_options(
+        client,
+        note_code,
+        account_library,
+        creator_account,
+        assets,
+        note_inputs,
+        NoteOptions::default(),
+    )
+    .await
+}
+
+/// Options for `create_public_note_with_options`.
+#[derive(Debug, Clone, Copy)]
+pub struct NoteOptions<'a> {
+    /// A cache used to reuse the compiled note script across calls, or `None` to compile it on
+    /// every call.
+    pub cache: Option<&'a LibraryCache>,
+    /// How many times to draw a new serial number if the note ID is already known to the client,
+    /// see `create_note_from_script_with_serials`.
+    pub collision_retries: u32,
+}
+
+impl Default for NoteOptions<'_> {
+    /// The options used by `create_public_note`: no cache and `NOTE_COLLISION_RETRIES` retries.
+    fn default() -> Self {
+        Self {
+            cache: None,
+            collision_retries: NOTE_COLLISION_RETRIES,
+        }
+    }
+}
+
+impl<'a> NoteOptions<'a> {
+    /// Sets the cache used to compile the note script.
+    pub fn with_cache(mut self, cache: &'a LibraryCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets how many times a new serial number is drawn on a note ID collision.
+    pub fn with_collision_retries(mut self, collision_retries: u32) -> Self {
+        self.collision_retries = collision_retries;
+        self
+    }
+}
+
+/// Creates a public note in the blockchain, with options.
+///
+/// Like `create_public_note`, but the compiled note script can be taken from a `LibraryCache`
+/// and the number of serial number retries on a note ID collision can be set.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `note_code` - The code for the note, typically written in MASM.
+/// * `account_library` - An optional library that might be used during note creation.
+/// * `creator_account` - The account creating the note.
+/// * `assets` - The assets associated with the note (optional).
+/// * `note_inputs` - The inputs associated with the note (optional).
+/// * `options` - The cache and collision retries to use.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the created `Note`, or `ToolsError::InvalidScript` if the note
+/// code does not compile.
+pub async fn create_public_note_with_options<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    note_code: String,
+    account_library: Option<Library>,
+    creator_account: Account,
+    assets: Option<NoteAssets>,
+    note_inputs: Option<NoteInputs>,
+    options: NoteOptions<'_>,
+) -> Result<Note, ToolsError> {
+    let note_script = match options.cache {
+        Some(cache) => cache.note_script(&note_code, account_library.as_ref())?,
+        None => compile_note_script(&note_code, account_library.as_ref())?,
+    };
+    create_note_from_script_with_serials(
+        client,
+        note_script,
+        &creator_account,
+        assets,
+        note_inputs,
+        NoteType::Public,
+        |client| client.rng().draw_word(),
+        options.collision_retries,
+    )
+    .await
+}
+
+/// Number of times `create_note_from_script` draws a new serial number on a note ID collision.
+pub const NOTE_COLLISION_RETRIES: u32 = 3;
+
+/// Creates a note from an already compiled note script.
+///
+/// Like `create_public_note`, but without compiling anything, so a script compiled once (or
+/// taken from `LibraryCache` or `WellKnownNote`) can be reused across notes. Public notes get
+/// the tag used by `create_public_note`; private notes get a local use-case tag, since public
+/// use-case tags are rejected for them.
+///
+/// Serial numbers are drawn from the client's RNG, with up to `NOTE_COLLISION_RETRIES` retries
+/// on a note ID collision (see `create_note_from_script_with_serials`).
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `script` - The compiled note script.
+/// * `creator` - The account creating the note.
+/// * `assets` - The assets associated with the note (optional).
+/// * `inputs` - The inputs associated with the note (optional).
+/// * `note_type` - The type of the note.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the created `Note`, or `ToolsError::NoteIdCollision` if every
+/// serial number collided.
+pub async fn create_note_from_script<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    script: NoteScript,
+    creator: &Account,
+    assets: Option<NoteAssets>,
+    inputs: Option<NoteInputs>,
+    note_type: NoteType,
+) -> Result<Note, ToolsError> {
+    create_note_from_script_with_serials(
+        client,
+        script,
+        creator,
+        assets,
+        inputs,
+        note_type,
+        |client| client.rng().draw_word(),
+        NOTE_COLLISION_RETRIES,
+    )
+    .await
+}
+
+/// Creates a note from a compiled note script, drawing serial numbers from `next_serial`.
+///
+/// Two notes with the same script, inputs, assets and serial number have the same ID. A new
+/// serial number is drawn, up to `max_retries` times, whenever the note ID is already among the
+/// client's input and output notes, or the node rejects the transaction because the note
+/// already exists (e.g. it was created by another client).
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `script` - The compiled note script.
+/// * `creator` - The account creating the note.
+/// * `assets` - The assets associated with the note (optional).
+/// * `inputs` - The inputs associated with the note (optional).
+/// * `note_type` - The type of the note.
+/// * `next_serial` - Draws the serial number for each attempt.
+/// * `max_retries` - The maximum number of serial numbers drawn after the first one.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the created `Note`, or `ToolsError::NoteIdCollision` with the
+/// last note ID tried if every serial number collided.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_note_from_script_with_serials<AUTH, S>(
+    client: &mut Client<AUTH>,
+    script: NoteScript,
+    creator: &Account,
+    assets: Option<NoteAssets>,
+    inputs: Option<NoteInputs>,
+    note_type: NoteType,
+    mut next_serial: S,
+    max_retries: u32,
+) -> Result<Note, ToolsError>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    S: FnMut(&mut Client<AUTH>) -> Word,
+{
+    let inputs = inputs.unwrap_or_else(|| NoteInputs::new([].to_vec()).unwrap());
+    let assets = assets.unwrap_or_else(|| NoteAssets::new(vec![]).unwrap());
+
+    let tag = match note_type {
+        NoteType::Public => public_tag(0, 0, NoteExecutionMode::Local).unwrap(),
+        _ => NoteTag::for_local_use_case(0, 0).unwrap(),
+    };
+    let metadata = NoteMetadata::new(
+        creator.id(),
+        note_type,
+        tag,
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )
+    .unwrap();
+
+    let mut attempt = 0;
+    loop {
+        let serial_num = next_serial(client);
+        let recipient = NoteRecipient::new(serial_num, script.clone(), inputs.clone());
+        let note = Note::new(assets.clone(), metadata, recipient);
+
+        let note_id = note.id();
+        let known = client.get_output_note(note_id).await?.is_some()
+            || client.get_input_note(note_id).await?.is_some();
+        if !known {
+            let note_req = TransactionRequestBuilder::new()
+                .own_output_notes(vec![OutputNote::Full(note.clone())])
+                .build()?;
+            let tx_result = client.new_transaction(creator.id(), note_req).await?;
+
+            match submit_transaction_with_retry(
+                client,
+                tx_result,
+                SUBMIT_RETRIES,
+                SUBMIT_RETRY_DELAY,
+            )
+            .await
+            {
+                Ok(()) => {
+                    client.sync_state().await?;
+                    return Ok(note);
+                }
+                Err(err) if is_duplicate_note_error(&err) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        if attempt >= max_retries {
+            return Err(ToolsError::NoteIdCollision(note_id));
+        }
+        attempt += 1;
+        tracing::warn!(%note_id, attempt, max_retries, "note id already exists, retrying");
+    }
+}
+
+/// Returns whether the node rejected a transaction because one of its output notes already
+/// exists.
+fn is_duplicate_note_error(err: &ClientError) -> bool {
+    let ClientError::RpcError(err) = err else {
+        return false;
+    };
+    match grpc_status_code(err) {
+        Some(tonic::Code::AlreadyExists) => true,
+        Some(tonic::Code::InvalidArgument) => matches!(
+            err,
+            RpcError::RequestError(_, message) if message.contains("notes already exist")
+        ),
+        _ => false,
+    }
+}
+
+/// Consumes a note, supplying advice map entries its script reads.
+///
+/// Notes cannot carry advice: the advice map is neither committed to by the note ID nor stored
+/// on-chain, and no helper in this crate keeps it alongside a note. Create the note as usual
+/// (e.g. with `create_public_note`) and deliver the entries to the consumer separately; this
+/// helper adds them to the advice map of the consuming transaction, where the script reads them
+/// with `adv.push_mapval`.
+///
+/// The note is consumed as an unauthenticated note, so it does not need to be committed yet.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account consuming the note.
+/// * `note` - The note to consume.
+/// * `advice_map` - The advice map entries the note script reads, as `(key, values)` pairs.
+///
+/// # Returns
+///
+/// Returns the ID of the consuming transaction.
+pub async fn consume_note_with_advice<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    note: &Note,
+    advice_map: Vec<(Word, Vec<Felt>)>,
+) -> Result<TransactionId, ClientError> {
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(note.clone(), None)])
+        .extend_advice_map(advice_map)
+        .build()?;
+
+    let tx_exec = client.new_transaction(account_id, consume_req).await?;
+    let tx_id = tx_exec.executed_transaction().id();
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    Ok(tx_id)
+}
+
+/// Creates a private note and returns the details the recipient needs to consume it.
+///
+/// Only the note commitment is published on-chain, so the note itself has to be delivered
+/// out-of-band. The returned blob is a serialized `NoteFile::NoteDetails`, which the recipient
+/// registers with `import_note_bytes`. The file carries the note tag and the block the note was
+/// created after, so the recipient's client picks up the note's inclusion on its next sync.
+///
+/// The note is created with `create_note_from_script_with_serials`, retrying up to
+/// `NOTE_COLLISION_RETRIES` times on a note ID collision.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `note_code` - The code for the note, typically written in MASM.
+/// * `account_library` - An optional library that might be used during note creation.
+/// * `creator_account` - The account creating the note.
+/// * `assets` - The assets associated with the note (optional).
+/// * `note_inputs` - The inputs associated with the note (optional).
+///
+/// # Returns
+///
+/// Returns the created `Note` and the serialized note file to share with the recipient.
+pub async fn create_private_note_with_details<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    note_code: String,
+    account_library: Option<Library>,
+    creator_account: Account,
+    assets: Option<NoteAssets>,
+    note_inputs: Option<NoteInputs>,
+) -> Result<(Note, Vec<u8>), ToolsError> {
+    let note_script = compile_note_script(&note_code, account_library.as_ref())?;
+
+    let after_block_num = client.get_sync_height().await?;
+    let note = create_note_from_script_with_serials(
+        client,
+        note_script,
+        &creator_account,
+        assets,
+        note_inputs,
+        NoteType::Private,
+        |client| client.rng().draw_word(),
+        NOTE_COLLISION_RETRIES,
+    )
+    .await?;
+
+    let note_file = NoteFile::NoteDetails {
+        details: note.clone().into(),
+        after_block_num,
+        tag: Some(note.metadata().tag()),
+    };
+
+    Ok((note, note_file.to_bytes()))
+}
+
+/// Registers a note received out-of-band in the client's store.
+///
+/// The note is imported by its details and tag, so the client looks for its inclusion from the
+/// genesis block on the next sync. Once imported it shows up among the client's input notes and
+/// can be consumed.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `note` - The note to import.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the note is stored, or a `ClientError` if the import fails.
+pub async fn import_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    note: Note,
+) -> Result<(), ClientError> {
+    let tag = note.metadata().tag();
+    let note_file = NoteFile::NoteDetails {
+        details: note.into(),
+        after_block_num: BlockNumber::GENESIS,
+        tag: Some(tag),
+    };
+    client.import_note(note_file).await?;
+    Ok(())
+}
+
+/// Registers a serialized note file in the client's store.
+///
+/// This accepts the blob returned by `create_private_note_with_details`, or any other
+/// serialized `NoteFile`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `bytes` - The serialized `NoteFile`.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the note is stored, or `ToolsError::Deserialization` if the bytes are not
+/// a note file.
+pub async fn import_note_bytes<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    bytes: &[u8],
+) -> Result<(), ToolsError> {
+    let note_file = NoteFile::read_from_bytes(bytes)?;
+    client.import_note(note_file).await?;
+    Ok(())
+}
+
+/// Imports every serialized note file found in a directory.
+///
+/// Files are read in name order. A file that does not deserialize as a `NoteFile` (or is not a
+/// regular file) is skipped with a message, while a note file the client fails to import is an
+/// error.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `dir` - The directory holding the serialized `NoteFile`s.
+///
+/// # Returns
+///
+/// Returns the number of notes imported, or `ToolsError::Io` if the directory cannot be read.
+pub async fn import_notes_from_dir<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    dir: impl AsRef<std::path::Path>,
+) -> Result<usize, ToolsError> {
+    let mut paths = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+
+    let mut imported = 0;
+    for path in paths.into_iter().filter(|path| path.is_file()) {
+        let bytes = std::fs::read(&path)?;
+        let Ok(note_file) = NoteFile::read_from_bytes(&bytes) else {
+            tracing::warn!(path = %path.display(), "skipping file that is not a note file");
+            continue;
+        };
+        client.import_note(note_file).await?;
+        imported += 1;
+    }
+
+    Ok(imported)
+}
+
+/// Waits for the exact note to be available and committed.
+///
+/// This function will block until the specified note is found in the output notes and is committed.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `expected` - The note to wait for.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating whether the note was found and committed.
+pub async fn wait_for_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    expected: &Note,
+) -> Result<(), ClientError> {
+    loop {
+        client.sync_state().await?;
+
+        let notes = client.get_output_notes(NoteFilter::All).await?;
+
+        // Check if the expected note is in the output notes and is committed
+        let found = notes
+            .iter()
+            .any(|output_note| output_note.id() == expected.id() && output_note.is_committed());
+
+        if found {
+            println!("✅ note found and committed {}", expected.id().to_hex());
+            break;
+        }
+
+        println!("Note {} not found. Waiting...", expected.id().to_hex());
+        sleep(Duration::from_secs(3)).await;
+    }
+    Ok(())
+}
+
+/// Creates a transaction script based on the provided code and optional library.
+///
+/// Use `LibraryCache::tx_script` instead to reuse the compiled script across calls.
+///
+/// # Arguments
+///
+/// * `script_code` - The code for the transaction script, typically written in MASM.
+/// * `library` - An optional library to use with the script.
+///
+/// # Returns
+///
+/// Returns a `TransactionScript` if successfully created, or an error if the script does not
+/// compile.
+pub fn create_tx_script(
+    script_code: String,
+    library: Option<Library>,
+) -> Result<TransactionScript, Error> {
+    compile_tx_script(&script_code, library.as_ref()).map_err(<Error as serde::de::Error>::custom)
+}
+
+/// Creates a public-to-ID (p2id) note for a specified sender and target account.
+///
+/// # Arguments
+///
+/// * `sender` - The account ID of the sender.
+/// * `target` - The account ID of the target.
+/// * `assets` - The assets associated with the note.
+/// * `note_type` - The type of the note (e.g., public).
+/// * `aux` - Auxiliary data for the note.
+/// * `serial_num` - The serial number of the note.
+///
+/// # Returns
+///
+/// Returns the created `Note`.
+pub fn create_exact_p2id_note(
+    sender: AccountId,
+    target: AccountId,
+    assets: Vec<Asset>,
+    note_type: NoteType,
+    aux: Felt,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    let recipient = utils::build_p2id_recipient(target, serial_num)?;
+    let tag = account_tag(target);
+
+    let metadata = NoteMetadata::new(sender, note_type, tag, NoteExecutionHint::always(), aux)?;
+    let vault = NoteAssets::new(assets)?;
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+/// Routing data carried in a note's `aux` field, see `encode_aux`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RoutingInfo {
+    /// The version of the routing scheme.
+    pub version: u8,
+    /// An application-defined channel.
+    pub channel: u16,
+    /// An application-defined destination.
+    pub destination: u32,
+}
+
+/// Encodes routing data into a note `aux` value.
+///
+/// The bit layout, from the least significant bit, is:
+///
+/// | bits    | field         |
+/// |---------|---------------|
+/// | 0..32   | `destination` |
+/// | 32..48  | `channel`     |
+/// | 48..56  | `version`     |
+/// | 56..64  | zero          |
+///
+/// The encoded value is below `2^56`, so it always fits in a `Felt` (whose modulus is
+/// `2^64 - 2^32 + 1`) and never wraps. The result can be passed as the `aux` argument of the
+/// note helpers, such as `create_exact_p2id_note`.
+///
+/// # Arguments
+///
+/// * `routing` - The routing data to encode.
+///
+/// # Returns
+///
+/// Returns the `aux` value.
+pub fn encode_aux(routing: RoutingInfo) -> Felt {
+    let aux = u64::from(routing.destination)
+        | (u64::from(routing.channel) << 32)
+        | (u64::from(routing.version) << 48);
+    Felt::new(aux)
+}
+
+/// Decodes routing data from a note `aux` value written by `encode_aux`.
+///
+/// The top 8 bits are ignored, so `decode_aux(encode_aux(routing)) == routing` for any routing.
+///
+/// # Arguments
+///
+/// * `aux` - The `aux` value of a note.
+///
+/// # Returns
+///
+/// Returns the decoded `RoutingInfo`.
+pub fn decode_aux(aux: Felt) -> RoutingInfo {
+    let aux = aux.as_int();
+    RoutingInfo {
+        version: (aux >> 48) as u8,
+        channel: (aux >> 32) as u16,
+        destination: aux as u32,
+    }
+}
+
+/// Builds `NoteAssets`, merging fungible assets issued by the same faucet.
+///
+/// `NoteAssets::new` rejects two fungible assets of the same faucet; here their amounts are
+/// added up into a single asset instead, at the position of the first one. Non-fungible assets
+/// cannot be merged, so a repeated one is an error.
+///
+/// # Arguments
+///
+/// * `assets` - The assets of the note.
+///
+/// # Returns
+///
+/// Returns the `NoteAssets`, `ToolsError::AmountOverflow` if a merged amount exceeds the
+/// maximum, or `ToolsError::DuplicateNonFungibleAsset` for a repeated non-fungible asset.
+pub fn safe_note_assets(assets: Vec<Asset>) -> Result<NoteAssets, ToolsError> {
+    let mut merged: Vec<Asset> = Vec::with_capacity(assets.len());
+    for asset in assets {
+        match asset {
+            Asset::Fungible(fungible) => {
+                let existing = merged.iter_mut().find_map(|merged| match merged {
+                    Asset::Fungible(existing) if existing.faucet_id() == fungible.faucet_id() => {
+                        Some(existing)
+                    }
+                    _ => None,
+                });
+                match existing {
+                    Some(existing) => {
+                        *existing = existing
+                            .add(fungible)
+                            .map_err(|_| ToolsError::AmountOverflow(fungible.faucet_id()))?;
+                    }
+                    None => merged.push(asset),
+                }
+            }
+            Asset::NonFungible(non_fungible) => {
+                if merged.contains(&asset) {
+                    return Err(ToolsError::DuplicateNonFungibleAsset(non_fungible));
+                }
+                merged.push(asset);
+            }
+        }
+    }
+    Ok(NoteAssets::new(merged)?)
+}
+
+/// Derives a note serial number from a seed and an index by hashing them.
+///
+/// The result only depends on the inputs, so passing it to `create_exact_p2id_note` makes note
+/// IDs reproducible across runs without sharing RNG state. Both values are split into 32-bit
+/// limbs before hashing, so distinct inputs never map to the same field elements.
+///
+/// # Arguments
+///
+/// * `seed` - A seed shared by all serial numbers of one run or test.
+/// * `index` - The position of the serial number in the sequence.
+///
+/// # Returns
+///
+/// Returns the serial number as a `Word`.
+pub fn deterministic_serial(seed: u64, index: u64) -> Word {
+    let limbs = [seed, seed >> 32, index, index >> 32].map(|value| Felt::new(value & 0xffff_ffff));
+    Hasher::hash_elements(&limbs)
+}
+
+/// Consumes all notes consumable by an account that carry the given tag.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account that will consume the notes.
+/// * `tag` - Only consumable notes with this tag are consumed.
+///
+/// # Returns
+///
+/// Returns the number of notes consumed, or `ToolsError::NoMatchingNotes` if no consumable note
+/// carries the tag.
+pub async fn consume_notes_by_tag<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    tag: NoteTag,
+) -> Result<usize, ToolsError> {
+    client.sync_state().await?;
+
+    let note_ids: Vec<_> = client
+        .get_consumable_notes(Some(account.id()))
+        .await?
+        .into_iter()
+        .filter(|(record, _)| {
+            record
+                .metadata()
+                .is_some_and(|metadata| metadata.tag() == tag)
+        })
+        .map(|(record, _)| (record.id(), None))
+        .collect();
+
+    if note_ids.is_empty() {
+        return Err(ToolsError::NoMatchingNotes);
+    }
+    let count = note_ids.len();
+
+    let consume_req = TransactionRequestBuilder::new()
+        .authenticated_input_notes(note_ids)
+        .build()?;
+
+    let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    Ok(count)
+}
+
+/// Consumes the oldest consumable notes of an account first.
+///
+/// Committed notes the account can consume are ordered by the block they were included in, and
+/// by their position within that block; the first `limit` of them are consumed in one
+/// transaction. Notes that are not committed yet are left alone.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account that will consume the notes.
+/// * `limit` - The maximum number of notes to consume.
+///
+/// # Returns
+///
+/// Returns the IDs of the consumed notes, oldest first; no transaction is submitted if there is
+/// nothing to consume.
+pub async fn consume_notes_fifo<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    limit: usize,
+) -> Result<Vec<NoteId>, ClientError> {
+    client.sync_state().await?;
+
+    let mut notes: Vec<_> = client
+        .get_consumable_notes(Some(account.id()))
+        .await?
+        .into_iter()
+        .filter_map(|(record, _)| {
+            let location = record.inclusion_proof()?.location();
+            Some((
+                (location.block_num(), location.node_index_in_block()),
+                record.id(),
+            ))
+        })
+        .collect();
+    notes.sort_by_key(|(position, _)| *position);
+
+    let note_ids: Vec<NoteId> = notes
+        .into_iter()
+        .take(limit)
+        .map(|(_, note_id)| note_id)
+        .collect();
+    if note_ids.is_empty() {
+        return Ok(note_ids);
+    }
+
+    let consume_req = TransactionRequestBuilder::new()
+        .authenticated_input_notes(note_ids.iter().map(|note_id| (*note_id, None)))
+        .build()?;
+
+    let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    Ok(note_ids)
+}
+
+/// Creates a public-to-ID (p2id) note with a random serial number drawn from the client RNG.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client whose RNG is used to draw the serial number.
+/// * `sender` - The account ID of the sender.
+/// * `target` - The account ID of the target.
+/// * `assets` - The assets associated with the note.
+/// * `note_type` - The type of the note (e.g., public).
+///
+/// # Returns
+///
+/// Returns the created `Note`. Use `create_exact_p2id_note` when a deterministic serial number
+/// is required.
+pub fn create_p2id_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    sender: AccountId,
+    target: AccountId,
+    assets: Vec<Asset>,
+    note_type: NoteType,
+) -> Result<Note, NoteError> {
+    let serial_num = client.rng().draw_word();
+    create_exact_p2id_note(sender, target, assets, note_type, Felt::new(0), serial_num)
+}
+
+/// Waits until the note is committed and buried under a number of blocks.
+///
+/// A note counts as confirmed once the chain tip is at least `confirmations` blocks above the
+/// block the note was included in, i.e. `confirmations = 0` behaves like `wait_for_note`. If a
+/// committed note disappears again (e.g. because of a shallow reorg), the wait starts over.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `expected` - The note to wait for.
+/// * `confirmations` - The number of blocks that must be built on top of the inclusion block.
+/// * `timeout` - The maximum time to wait.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the note is confirmed, or `ToolsError::Timeout` if the timeout elapses.
+pub async fn wait_for_note_confirmed<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    expected: &Note,
+    confirmations: u32,
+    timeout: Duration,
+) -> Result<(), ToolsError> {
+    let deadline = Instant::now() + timeout;
+    let mut seen_committed = false;
+
+    loop {
+        client.sync_state().await?;
+        let tip = client.get_sync_height().await?.as_u32();
+
+        let inclusion_block = client
+            .get_output_notes(NoteFilter::Unique(expected.id()))
+            .await?
+            .iter()
+            .filter(|output_note| output_note.is_committed())
+            .find_map(|output_note| output_note.inclusion_proof())
+            .map(|proof| proof.location().block_num().as_u32());
+
+        match inclusion_block {
+            Some(block_num) if tip.saturating_sub(block_num) >= confirmations => {
+                tracing::info!(
+                    note_id = %expected.id(),
+                    confirmations = tip - block_num,
+                    "note confirmed"
+                );
+                return Ok(());
+            }
+            Some(_) => seen_committed = true,
+            None if seen_committed => {
+                tracing::info!(note_id = %expected.id(), "note no longer committed, restarting wait");
+                seen_committed = false;
+            }
+            None => {}
+        }
+
+        if Instant::now() >= deadline {
+            return Err(ToolsError::Timeout);
+        }
+
+        tracing::debug!(note_id = %expected.id(), "note not confirmed yet, waiting");
+        sleep(Duration::from_secs(3)).await;
+    }
+}
+
+/// Waits until a note created by a transaction is committed and returns its input note record.
+///
+/// The record is the client's consumable view of the note, including its inclusion proof, so it
+/// can be fed straight into `authenticated_input_notes`. The client only stores input notes that
+/// are relevant to the accounts or tags it tracks, e.g. a note minted to one of its accounts.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `tx` - The transaction that created the note.
+/// * `index` - The position of the note among the transaction's created notes.
+/// * `timeout` - The maximum time to wait.
+///
+/// # Returns
+///
+/// Returns the committed `InputNoteRecord`, `ToolsError::NoMatchingNotes` if the transaction
+/// created fewer than `index + 1` notes, or `ToolsError::Timeout` if the timeout elapses.
+pub async fn await_created_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    tx: &TransactionResult,
+    index: usize,
+    timeout: Duration,
+) -> Result<InputNoteRecord, ToolsError> {
+    let note_id = tx
+        .created_notes()
+        .iter()
+        .nth(index)
+        .ok_or(ToolsError::NoMatchingNotes)?
+        .id();
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        client.sync_state().await?;
+
+        let committed = client
+            .get_input_note(note_id)
+            .await?
+            .filter(|record| record.inclusion_proof().is_some());
+        if let Some(record) = committed {
+            tracing::info!(note_id = %note_id.to_hex(), "note found and committed");
+            return Ok(record);
+        }
+
+        if Instant::now() >= deadline {
+            return Err(ToolsError::Timeout);
+        }
+
+        tracing::debug!(note_id = %note_id.to_hex(), "note not committed yet, waiting");
+        sleep(Duration::from_secs(3)).await;
+    }
+}
+
+/// Waits until several accounts each hold at least an expected balance.
+///
+/// Every poll syncs the client and checks all expectations against the stored account state, so
+/// the balances only count once the accounts have consumed their notes.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `expectations` - Tuples of `(account_id, faucet_id, min_balance)`.
+/// * `timeout` - The maximum time to wait.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once every expectation is met, or `ToolsError::BalancesNotReached` with the
+/// expectations still unmet when the timeout elapses.
+pub async fn wait_for_balances<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    expectations: &[(AccountId, AccountId, u64)],
+    timeout: Duration,
+) -> Result<(), ToolsError> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        client.sync_state().await?;
+
+        let mut unmet = Vec::new();
+        for &(account_id, faucet_id, min_balance) in expectations {
+            let balance = client
+                .get_account(account_id)
+                .await?
+                .and_then(|record| record.account().vault().get_balance(faucet_id).ok())
+                .unwrap_or(0);
+            if balance < min_balance {
+                unmet.push((account_id, faucet_id, min_balance));
+            }
+        }
+
+        if unmet.is_empty() {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(ToolsError::BalancesNotReached(unmet));
+        }
+
+        tracing::debug!(unmet = unmet.len(), "balances not reached yet, waiting");
+        sleep(Duration::from_secs(3)).await;
+    }
+}
+
+/// Increments the counter of an account deployed with `masm/accounts/counter.masm`.
+///
+/// The bundled `increment_script.masm` is compiled against the account library and submitted as
+/// a transaction script against the account.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The counter account.
+/// * `library` - The counter contract library, linked under `external_contract::counter_contract`.
+///
+/// # Returns
+///
+/// Returns the counter value read from storage slot 0 after the transaction, or
+/// `ToolsError::InvalidScript` if the script does not compile against `library`.
+pub async fn increment_counter<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    library: Library,
+) -> Result<u64, ToolsError> {
+    let tx_script = compile_tx_script(
+        include_str!("../masm/scripts/increment_script.masm"),
+        Some(&library),
+    )?;
+
+    let tx_req = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()?;
+
+    let tx_exec = client.new_transaction(account.id(), tx_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    let record = client
+        .get_account(account.id())
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(account.id()))?;
+    let count = record
+        .account()
+        .storage()
+        .get_item(0)
+        .map_err(ClientError::AccountError)?;
+
+    Ok(count[3].as_int())
+}
+
+/// Submits a transaction that does nothing except advance the account nonce.
+///
+/// The request has no notes and no script, so the only state change is the nonce increment done
+/// by the account's auth component. This requires an auth component that increments the nonce
+/// on every transaction, like the `AuthRpoFalcon512` component of accounts created with
+/// `create_basic_account`. Accounts using `NoAuth` only increment the nonce when their state
+/// changes, so for them the transaction is empty and fails to execute.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account whose nonce to advance.
+///
+/// # Returns
+///
+/// Returns the ID of the submitted transaction.
+pub async fn submit_noop_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+) -> Result<TransactionId, ClientError> {
+    let tx_req = TransactionRequestBuilder::new().build()?;
+
+    let tx_exec = client.new_transaction(account.id(), tx_req).await?;
+    let tx_id = tx_exec.executed_transaction().id();
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    Ok(tx_id)
+}
+
+/// Creates a basic account and advances its nonce with a number of no-op transactions.
+///
+/// A new account has nonce 0 and every transaction submitted by `submit_noop_transaction`
+/// increments it by one, so the final nonce equals `increments`. With `increments = 0` the
+/// account is only created locally, like with `create_basic_account`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `keystore` - The keystore to store the account's secret key.
+/// * `increments` - The number of no-op transactions to submit.
+///
+/// # Returns
+///
+/// Returns the account as stored after the last transaction, together with its final nonce.
+pub async fn create_account_and_advance_nonce<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    increments: u32,
+) -> Result<(Account, u64), ClientError> {
+    let (account, _) = create_basic_account(client, keystore).await?;
+    client.sync_state().await?;
+
+    for _ in 0..increments {
+        submit_noop_transaction(client, &account).await?;
+    }
+
+    let account = client
+        .get_account(account.id())
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(account.id()))?
+        .account()
+        .clone();
+    let nonce = account.nonce().as_int();
+
+    Ok((account, nonce))
+}
+
+/// The state of an account at one point in time, taken by `snapshot_account`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountSnapshot {
+    /// The account the snapshot was taken of.
+    pub account_id: AccountId,
+    /// The account nonce.
+    pub nonce: u64,
+    /// The value of every storage slot, by index. Map slots are represented by their root.
+    pub storage: Vec<Word>,
+    /// The fungible asset balances, by faucet.
+    pub balances: BTreeMap<AccountId, u64>,
+}
+
+/// The changes between two snapshots of an account, computed by `diff_snapshots`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// How much the nonce increased.
+    pub nonce_delta: u64,
+    /// The storage slots that changed, as `(index, before, after)`.
+    pub storage_changes: Vec<(u8, Word, Word)>,
+    /// The fungible balances that changed, as `(faucet_id, before, after)`.
+    pub balance_changes: Vec<(AccountId, u64, u64)>,
+}
+
+impl AccountDiff {
+    /// Returns `true` if nothing changed between the two snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.nonce_delta == 0 && self.storage_changes.is_empty() && self.balance_changes.is_empty()
+    }
+}
+
+/// Takes a snapshot of the nonce, storage and fungible balances of an account.
+///
+/// The snapshot reflects the client's store, so sync first to capture the latest on-chain state.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account to snapshot.
+///
+/// # Returns
+///
+/// Returns the `AccountSnapshot`, or `ClientError::AccountDataNotFound` if the account is not
+/// tracked.
+pub async fn snapshot_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+) -> Result<AccountSnapshot, ClientError> {
+    let record = client
+        .get_account(account_id)
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(account_id))?;
+    let account = record.account();
+
+    let storage = account
+        .storage()
+        .slots()
+        .iter()
+        .map(|slot| slot.value())
+        .collect();
+
+    let mut balances = BTreeMap::new();
+    for asset in account.vault().assets() {
+        if let Asset::Fungible(asset) = asset {
+            *balances.entry(asset.faucet_id()).or_insert(0) += asset.amount();
+        }
+    }
+
+    Ok(AccountSnapshot {
+        account_id,
+        nonce: account.nonce().as_int(),
+        storage,
+        balances,
+    })
+}
+
+/// Computes what changed between two snapshots of the same account.
+///
+/// Storage slots missing from one of the snapshots are compared as empty words, and faucets
+/// missing from one of the snapshots as a zero balance.
+///
+/// # Arguments
+///
+/// * `before` - The earlier snapshot.
+/// * `after` - The later snapshot.
+///
+/// # Returns
+///
+/// Returns the `AccountDiff`; it is empty if the account did not change.
+pub fn diff_snapshots(before: &AccountSnapshot, after: &AccountSnapshot) -> AccountDiff {
+    let num_slots = before.storage.len().max(after.storage.len());
+    let storage_changes = (0..num_slots)
+        .filter_map(|index| {
+            let old = before.storage.get(index).copied().unwrap_or_default();
+            let new = after.storage.get(index).copied().unwrap_or_default();
+            (old != new).then_some((index as u8, old, new))
+        })
+        .collect();
+
+    let faucet_ids: BTreeSet<AccountId> = before
+        .balances
+        .keys()
+        .chain(after.balances.keys())
+        .copied()
+        .collect();
+    let balance_changes = faucet_ids
+        .into_iter()
+        .filter_map(|faucet_id| {
+            let old = before.balances.get(&faucet_id).copied().unwrap_or(0);
+            let new = after.balances.get(&faucet_id).copied().unwrap_or(0);
+            (old != new).then_some((faucet_id, old, new))
+        })
+        .collect();
+
+    AccountDiff {
+        nonce_delta: after.nonce.saturating_sub(before.nonce),
+        storage_changes,
+        balance_changes,
+    }
+}
+
+/// Consumes a note and checks that an account balance changed by exactly the expected amount.
+///
+/// The account is snapshotted with `snapshot_account` before and after consuming the note as an
+/// unauthenticated input note, and the change of the `faucet_id` balance is compared with
+/// `expected_delta`. A negative delta is expected when the note script takes assets from the
+/// account.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account consuming the note.
+/// * `note` - The note to consume.
+/// * `expected_delta` - The expected change of the balance.
+/// * `faucet_id` - The faucet whose balance to check.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the balance changed as expected, or `ToolsError::UnexpectedDelta` with
+/// the actual change.
+pub async fn consume_and_assert_delta<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    note: Note,
+    expected_delta: i64,
+    faucet_id: AccountId,
+) -> Result<(), ToolsError> {
+    let before = snapshot_account(client, account.id()).await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(note, None)])
+        .build()?;
+    let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    let after = snapshot_account(client, account.id()).await?;
+    let balance = |snapshot: &AccountSnapshot| {
+        i128::from(snapshot.balances.get(&faucet_id).copied().unwrap_or(0))
+    };
+    // balances are at most `FungibleAsset::MAX_AMOUNT`, so the delta always fits in an i64
+    let actual = (balance(&after) - balance(&before)) as i64;
+
+    if actual != expected_delta {
+        return Err(ToolsError::UnexpectedDelta {
+            faucet_id,
+            expected: expected_delta,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a flow left no half-finished notes behind for an account.
+///
+/// After syncing, this fails if the account can still consume a committed note, or if a note
+/// the account created is still waiting to be committed. Notes that are tracked but not yet
+/// committed on the consuming side are not counted, since they cannot be consumed as
+/// authenticated notes yet.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account whose notes to check.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if nothing is left, or `ToolsError::DanglingNotes` with the IDs of the
+/// leftover notes.
+pub async fn assert_no_dangling_notes<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+) -> Result<(), ToolsError> {
+    client.sync_state().await?;
+
+    let unconsumed: Vec<NoteId> = client
+        .get_consumable_notes(Some(account.id()))
+        .await?
+        .into_iter()
+        .filter(|(record, _)| record.inclusion_proof().is_some())
+        .map(|(record, _)| record.id())
+        .collect();
+    let uncommitted: Vec<NoteId> = client
+        .get_output_notes(NoteFilter::Expected)
+        .await?
+        .into_iter()
+        .filter(|record| record.metadata().sender() == account.id())
+        .map(|record| record.id())
+        .collect();
+
+    if !unconsumed.is_empty() || !uncommitted.is_empty() {
+        return Err(ToolsError::DanglingNotes {
+            unconsumed,
+            uncommitted,
+        });
+    }
+    Ok(())
+}
+
+/// Whether an input note can be consumed as an authenticated note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteAuthStatus {
+    /// The note's inclusion proof is known, so it can be passed to
+    /// `authenticated_input_notes`.
+    Authenticated,
+    /// The note is tracked but not yet committed; it must be passed to
+    /// `unauthenticated_input_notes`.
+    Pending,
+    /// The note is not tracked by the client.
+    Unknown,
+}
+
+/// Returns the authentication status of an input note tracked by the client.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `note_id` - The ID of the note to check.
+///
+/// # Returns
+///
+/// Returns the `NoteAuthStatus` of the note based on the local store.
+pub async fn note_auth_status<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    note_id: NoteId,
+) -> Result<NoteAuthStatus, ClientError> {
+    let status = match client.get_input_note(note_id).await? {
+        Some(record) if record.inclusion_proof().is_some() => NoteAuthStatus::Authenticated,
+        Some(_) => NoteAuthStatus::Pending,
+        None => NoteAuthStatus::Unknown,
+    };
+    Ok(status)
+}
+
+/// Rebuilds a full `Note` from an input note record of the client's store.
+///
+/// Records imported from `NoteFile::NoteDetails` (e.g. private notes shared out-of-band) carry
+/// the note details but no metadata until the note is found on-chain, so they cannot be turned
+/// into a `Note` yet. The returned note can be passed to helpers taking notes, such as
+/// `consume_notes_deadline`.
+///
+/// # Arguments
+///
+/// * `record` - The input note record, e.g. from `get_consumable_notes`.
+///
+/// # Returns
+///
+/// Returns the `Note`, or `ToolsError::IncompleteNoteRecord` if the record has no metadata.
+pub fn record_to_note(record: &InputNoteRecord) -> Result<Note, ToolsError> {
+    record
+        .clone()
+        .try_into()
+        .map_err(|_| ToolsError::IncompleteNoteRecord(record.id()))
+}
+
+/// Whether an account has been recorded on-chain, as returned by `account_exists_on_chain`.
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountOnChainStatus {
+    /// The node knows the account.
+    Exists,
+    /// The node does not know the account; it has not executed a transaction yet.
+    NotFound,
+    /// The account is private, so its state cannot be queried.
+    Unknown,
+}
+
+/// Checks with the node whether an account exists on-chain, e.g. before sending it a note.
+///
+/// Accounts only appear on-chain once their first transaction is committed. Private accounts
+/// are not queried and yield `AccountOnChainStatus::Unknown`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account to look up.
+///
+/// # Returns
+///
+/// Returns the `AccountOnChainStatus` of the account, or a `ClientError` if the node cannot be
+/// reached or fails for any other reason than not knowing the account.
+#[cfg(feature = "testing")]
+pub async fn account_exists_on_chain<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+) -> Result<AccountOnChainStatus, ClientError> {
+    if !account_id.is_public() {
+        return Ok(AccountOnChainStatus::Unknown);
+    }
+
+    match client.test_rpc_api().get_account_details(account_id).await {
+        Ok(_) => Ok(AccountOnChainStatus::Exists),
+        Err(err) if is_not_found(&err) => Ok(AccountOnChainStatus::NotFound),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Returns whether the node rejected a request because the requested item does not exist.
+#[cfg(feature = "testing")]
+fn is_not_found(err: &RpcError) -> bool {
+    grpc_status_code(err) == Some(tonic::Code::NotFound)
+}
+
+/// Checks that a fungible transfer between two accounts can go through, before building a note.
+///
+/// The target counts as existing if the client tracks it or `account_exists_on_chain` does not
+/// report it as `AccountOnChainStatus::NotFound`; private targets cannot be looked up and are
+/// accepted.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `from` - The sending account; its balance is read from the client store.
+/// * `to` - The receiving account.
+/// * `faucet_id` - The faucet of the asset to transfer.
+/// * `amount` - The amount to transfer.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the transfer is feasible, or `ToolsError::ZeroAmount`,
+/// `ToolsError::NotAFaucet`, `ToolsError::InsufficientAmount` or `ToolsError::TargetNotFound`
+/// for the first check that fails. Errors from the node lookup are returned as they are.
+#[cfg(feature = "testing")]
+pub async fn check_transfer_feasible<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    from: &Account,
+    to: AccountId,
+    faucet_id: AccountId,
+    amount: u64,
+) -> Result<(), ToolsError> {
+    if amount == 0 {
+        return Err(ToolsError::ZeroAmount);
+    }
+    if faucet_id.account_type() != AccountType::FungibleFaucet {
+        return Err(ToolsError::NotAFaucet(faucet_id));
+    }
+
+    let available = client
+        .get_account(from.id())
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(from.id()))?
+        .account()
+        .vault()
+        .get_balance(faucet_id)
+        .map_err(ClientError::AssetError)?;
+    if available < amount {
+        return Err(ToolsError::InsufficientAmount {
+            available,
+            requested: amount,
+        });
+    }
+
+    if client.get_account(to).await?.is_none()
+        && account_exists_on_chain(client, to).await? == AccountOnChainStatus::NotFound
+    {
+        return Err(ToolsError::TargetNotFound(to));
+    }
+
+    Ok(())
+}
+
+/// Executes a transaction request against an account and submits it.
+///
+/// When `with_error_chain` is set and execution fails, the returned `ToolsError::ExecutionFailed`
+/// carries the messages of the error's `source()` chain, down to the VM error. This is the
+/// information the error already holds, flattened for printing: the transaction is not
+/// re-executed, so no VM trace or operand stack is captured. The VM error names the clock cycle
+/// of the failure, and since `instantiate_client` runs the client in debug mode it also carries
+/// the MASM source location.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account executing the transaction.
+/// * `tx_request` - The transaction request to execute.
+/// * `with_error_chain` - Whether to collect the error chain on execution failure.
+///
+/// # Returns
+///
+/// Returns the submitted `TransactionResult`.
+pub async fn execute_and_submit<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    tx_request: TransactionRequest,
+    with_error_chain: bool,
+) -> Result<TransactionResult, ToolsError> {
+    let tx_result = match client.new_transaction(account_id, tx_request).await {
+        Ok(tx_result) => tx_result,
+        Err(error) if with_error_chain => {
+            let mut error_chain = Vec::new();
+            let mut source = std::error::Error::source(&error);
+            while let Some(err) = source {
+                error_chain.push(err.to_string());
+                source = err.source();
+            }
+            return Err(ToolsError::ExecutionFailed { error, error_chain });
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    submit_transaction_with_retry(
+        client,
+        tx_result.clone(),
+        SUBMIT_RETRIES,
+        SUBMIT_RETRY_DELAY,
+    )
+    .await?;
+    Ok(tx_result)
+}
+
+/// Executes a transaction against the client's current state, without submitting it.
+///
+/// Historical simulation is not supported: the client only keeps the latest state of its
+/// accounts and notes, so transactions can only be executed against its sync height. To
+/// simulate at a specific block, sync the client to that block first (e.g. by pausing syncing
+/// with `SyncController` once it is reached). The block the transaction was executed against is
+/// returned along with it.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account executing the transaction.
+/// * `tx_request` - The transaction request to execute.
+///
+/// # Returns
+///
+/// Returns the executed `TransactionResult` and the sync height it was executed against.
+pub async fn simulate_at_sync_height<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    tx_request: TransactionRequest,
+) -> Result<(TransactionResult, BlockNumber), ClientError> {
+    let sync_height = client.get_sync_height().await?;
+    let tx_result = client.new_transaction(account_id, tx_request).await?;
+    Ok((tx_result, sync_height))
+}
+
+/// Formats a client error, e.g. from `new_transaction`, into a readable multi-line message.
+///
+/// Every error in the source chain gets its own `caused by` line, skipping messages already
+/// contained in an earlier line. VM execution errors additionally list the MASM source location
+/// and line of each labelled span; these are only available when the failing code was compiled
+/// in debug mode, as the helpers in this crate do.
+///
+/// # Arguments
+///
+/// * `err` - The error to format.
+///
+/// # Returns
+///
+/// Returns the formatted message.
+pub fn format_tx_error(err: &ClientError) -> String {
+    let mut out = err.to_string();
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        let message = cause.to_string();
+        if !out.contains(&message) {
+            out.push_str(&format!("\n  caused by: {message}"));
+        }
+        if let Some(execution_error) = cause.downcast_ref::<ExecutionError>() {
+            push_source_snippets(&mut out, execution_error);
+        }
+        source = cause.source();
+    }
+    out
+}
+
+/// Appends the source location and code of every label of `diagnostic` to `out`.
+fn push_source_snippets(out: &mut String, diagnostic: &dyn Diagnostic) {
+    let (Some(source_code), Some(labels)) = (diagnostic.source_code(), diagnostic.labels()) else {
+        return;
+    };
+
+    for label in labels {
+        let Ok(contents) = source_code.read_span(label.inner(), 0, 0) else {
+            continue;
+        };
+        out.push_str(&format!(
+            "\n  at {}:{}:{}",
+            contents.name().unwrap_or("<unknown>"),
+            contents.line() + 1,
+            contents.column() + 1
+        ));
+        if let Some(text) = label.label() {
+            out.push_str(&format!(" ({text})"));
+        }
+        out.push_str(&format!(
+            "\n    | {}",
+            String::from_utf8_lossy(contents.data()).trim()
+        ));
+    }
+}
+
+/// Executes a transaction request and submits it, signing with the given authenticator.
+///
+/// A client with `authenticator` in place of the keystore is opened on the store described by
+/// `config`, see `instantiate_client_with_authenticator`. The account must already be tracked by
+/// that store; only the signing is delegated.
+///
+/// # Arguments
+///
+/// * `config` - The configuration of the client the account was added to.
+/// * `authenticator` - The authenticator producing transaction signatures.
+/// * `account_id` - The account executing the transaction.
+/// * `tx_request` - The transaction request to execute.
+///
+/// # Returns
+///
+/// Returns the submitted `TransactionResult`.
+pub async fn execute_and_submit_with_authenticator<
+    AUTH: TransactionAuthenticator + Send + Sync + 'static,
+>(
+    config: ClientConfig,
+    authenticator: AUTH,
+    account_id: AccountId,
+    tx_request: TransactionRequest,
+) -> Result<TransactionResult, ToolsError> {
+    let mut client = instantiate_client_with_authenticator(config, authenticator).await?;
+    client.sync_state().await?;
+    execute_and_submit(&mut client, account_id, tx_request, false).await
+}
+
+/// Consumes a set of notes for each of several accounts.
+///
+/// One consume transaction is submitted per account. The client requires exclusive access for
+/// each transaction, so the transactions are submitted one after the other; a failure for one
+/// account does not stop the remaining accounts from being processed.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `assignments` - Pairs of an account and the notes it should consume.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if every account consumed its notes, or the errors of the accounts that
+/// failed, tagged with their account ID.
+pub async fn consume_for_accounts<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    assignments: &[(&Account, Vec<Note>)],
+) -> Result<(), Vec<(AccountId, ClientError)>> {
+    let mut errors = Vec::new();
+
+    for (account, notes) in assignments {
+        if notes.is_empty() {
+            continue;
+        }
+
+        let result = async {
+            let consume_req = TransactionRequestBuilder::new()
+                .unauthenticated_input_notes(notes.iter().map(|note| (note.clone(), None)))
+                .build()?;
+            let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+            submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY)
+                .await?;
+            Ok::<(), ClientError>(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            tracing::warn!(account_id = %account.id(), error = %err, "failed to consume notes");
+            errors.push((account.id(), err));
+        }
+    }
+
+    if let Err(err) = client.sync_state().await {
+        tracing::warn!(error = %err, "failed to sync after consuming notes");
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// How often `submit_pipeline` syncs while waiting for in-flight transactions to commit.
+const PIPELINE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Executes and submits independent transactions, keeping at most `concurrency` of them in
+/// flight.
+///
+/// The client requires exclusive access to execute and submit, so requests are still executed
+/// one after the other, in order; what is bounded is the number of submitted transactions not
+/// yet committed. Before a request is executed, the pipeline waits until fewer than
+/// `concurrency` transactions are in flight and until the previous transaction of the same
+/// account has left the mempool, so that transactions of one account never race for the same
+/// nonce. A failed request does not stop the remaining ones.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `requests` - The accounts and the transaction requests they execute.
+/// * `concurrency` - The maximum number of uncommitted transactions; `0` is treated as `1`.
+///
+/// # Returns
+///
+/// Returns the result of each request, in the order of `requests`.
+pub async fn submit_pipeline<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    requests: Vec<(AccountId, TransactionRequest)>,
+    concurrency: usize,
+) -> Vec<Result<TransactionId, ClientError>> {
+    let concurrency = concurrency.max(1);
+    let mut in_flight: Vec<(AccountId, TransactionId)> = Vec::new();
+    let mut results = Vec::with_capacity(requests.len());
+
+    for (account_id, tx_request) in requests {
+        let result = async {
+            while in_flight.len() >= concurrency
+                || in_flight.iter().any(|(id, _)| *id == account_id)
+            {
+                sleep(PIPELINE_POLL_INTERVAL).await;
+                retain_pending(client, &mut in_flight).await?;
+            }
+
+            let tx_result = client.new_transaction(account_id, tx_request).await?;
+            let tx_id = tx_result.executed_transaction().id();
+            submit_transaction_with_retry(client, tx_result, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY)
+                .await?;
+            in_flight.push((account_id, tx_id));
+            Ok(tx_id)
+        }
+        .await;
+
+        if let Err(err) = &result {
+            tracing::warn!(%account_id, error = %err, "pipelined transaction failed");
+        }
+        results.push(result);
+    }
+
+    results
+}
+
+/// Syncs the client and drops the transactions that are no longer pending from `in_flight`.
+async fn retain_pending<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    in_flight: &mut Vec<(AccountId, TransactionId)>,
+) -> Result<(), ClientError> {
+    client.sync_state().await?;
+    let ids = in_flight.iter().map(|(_, tx_id)| *tx_id).collect();
+    let pending: Vec<TransactionId> = client
+        .get_transactions(TransactionFilter::Ids(ids))
+        .await?
+        .into_iter()
+        .filter(|record| matches!(record.status, TransactionStatus::Pending))
+        .map(|record| record.id)
+        .collect();
+    in_flight.retain(|(_, tx_id)| pending.contains(tx_id));
+    Ok(())
+}
+
+/// Returns the transactions submitted by the client that are not committed yet.
+///
+/// The client does not sync first, so a transaction submitted just before is listed even if the
+/// node has committed it in the meantime; call `sync_state` first for an up-to-date list.
+///
+/// This crate does not provide a `discard_transaction`: the client has no way to discard a
+/// pending transaction by hand. It marks one as discarded itself during a later sync, once it
+/// expires (see `TransactionRequestBuilder::expiration_delta`) or its inputs are consumed by
+/// another transaction, which frees its input notes again. Discarding only affects the local
+/// store and would never undo a transaction the node has already accepted.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+///
+/// # Returns
+///
+/// Returns the records of the pending transactions.
+pub async fn list_pending_transactions<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+) -> Result<Vec<TransactionRecord>, ClientError> {
+    let pending = client
+        .get_transactions(TransactionFilter::Uncommitted)
+        .await?
+        .into_iter()
+        .filter(|record| matches!(record.status, TransactionStatus::Pending))
+        .collect();
+    Ok(pending)
+}
+
+/// The total balance of a fungible asset, as returned by `total_fungible_balance`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FungibleBalanceTotal {
+    /// The sum of the balances of the accounts that could be read.
+    pub total: u64,
+    /// The number of accounts whose state could not be read from the store.
+    pub skipped: usize,
+}
+
+/// Sums the balance of a fungible asset across all accounts tracked by the client.
+///
+/// Accounts whose state cannot be read from the store are skipped and counted in
+/// `FungibleBalanceTotal::skipped`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `faucet_id` - The faucet issuing the asset.
+///
+/// # Returns
+///
+/// Returns the total balance of the asset held by the tracked accounts together with the
+/// number of skipped accounts, or `ToolsError::AmountOverflow` if the sum exceeds `u64`.
+pub async fn total_fungible_balance<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    faucet_id: AccountId,
+) -> Result<FungibleBalanceTotal, ToolsError> {
+    let mut result = FungibleBalanceTotal::default();
+
+    for (header, _) in client.get_account_headers().await? {
+        let balance = client
+            .get_account(header.id())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|record| record.account().vault().get_balance(faucet_id).ok());
+
+        match balance {
+            Some(balance) => {
+                result.total = result
+                    .total
+                    .checked_add(balance)
+                    .ok_or(ToolsError::AmountOverflow(faucet_id))?;
+            }
+            None => result.skipped += 1,
+        }
+    }
+
+    Ok(result)
+}
+
+/// Checks that a note runs the expected script.
+///
+/// # Arguments
+///
+/// * `note` - The note to check.
+/// * `expected_digest` - The expected root of the note script.
+///
+/// # Returns
+///
+/// Returns `ToolsError::NoteScriptMismatch` if the note's script root differs.
+pub fn assert_note_script(note: &Note, expected_digest: Word) -> Result<(), ToolsError> {
+    let actual = note.script().root();
+    if actual != expected_digest {
+        return Err(ToolsError::NoteScriptMismatch {
+            expected: expected_digest,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a note is a P2ID note for the expected target account.
+///
+/// The P2ID recipient for `expected_target` is rebuilt with the note's serial number and its
+/// digest compared with the note's recipient digest. The digest commits to the script, the
+/// inputs (the target ID) and the serial number, so a match means the note is a P2ID note that
+/// only `expected_target` can consume.
+///
+/// # Arguments
+///
+/// * `note` - The note to check.
+/// * `expected_target` - The account that should be able to consume the note.
+///
+/// # Returns
+///
+/// Returns `ToolsError::RecipientMismatch` if the note is not a P2ID note for the target.
+pub fn assert_p2id_target(note: &Note, expected_target: AccountId) -> Result<(), ToolsError> {
+    let expected = utils::build_p2id_recipient(expected_target, note.serial_num())?.digest();
+    let actual = note.recipient().digest();
+    if actual != expected {
+        return Err(ToolsError::RecipientMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// How often `watch_notes` syncs with the node while waiting for new notes.
+pub const NOTE_WATCH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Returns a stream of output notes as they get committed.
+///
+/// The first poll records the output notes that are already committed; afterwards the stream
+/// syncs every `NOTE_WATCH_INTERVAL` and yields the ID of each output note that became committed
+/// since the previous poll. Polling only happens while the stream is being awaited.
+///
+/// The stream ends if syncing with the node or reading the store fails. To stop watching
+/// earlier, drop the stream or bound it with a combinator such as `take` or `take_until`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+///
+/// # Returns
+///
+/// Returns a stream of newly committed note IDs.
+pub fn watch_notes<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+) -> impl Stream<Item = NoteId> + '_ {
+    let state = (client, None::<HashSet<NoteId>>, VecDeque::new());
+
+    stream::unfold(state, |(client, mut seen, mut pending)| async move {
+        loop {
+            if let Some(note_id) = pending.pop_front() {
+                return Some((note_id, (client, seen, pending)));
+            }
+            if seen.is_some() {
+                sleep(NOTE_WATCH_INTERVAL).await;
+            }
+
+            client.sync_state().await.ok()?;
+            let committed = client
+                .get_output_notes(NoteFilter::Committed)
+                .await
+                .ok()?
+                .into_iter()
+                .map(|output_note| output_note.id());
+
+            match seen.as_mut() {
+                None => seen = Some(committed.collect()),
+                Some(seen) => pending.extend(committed.filter(|note_id| seen.insert(*note_id))),
+            }
+        }
+    })
+}
+
+/// Tracks the consumable notes already seen, to report only newly arrived ones.
+///
+/// Unlike `watch_notes`, the watcher does not own the client or sleep between polls: each call to
+/// `poll` syncs once and returns the notes that became consumable since the previous call, so it
+/// fits an existing event loop. A note is reported once, even if it stays consumable.
+#[derive(Debug, Clone, Default)]
+pub struct NoteWatcher {
+    account_id: Option<AccountId>,
+    seen: HashSet<NoteId>,
+}
+
+impl NoteWatcher {
+    /// Creates a watcher for the notes consumable by `account_id`, or by any tracked account for
+    /// `None`. Nothing has been seen yet, so the first poll returns every consumable note.
+    pub fn new(account_id: Option<AccountId>) -> Self {
+        Self {
+            account_id,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Syncs the client and returns the IDs of the consumable notes not seen by earlier polls.
+    pub async fn poll<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &mut self,
+        client: &mut Client<AUTH>,
+    ) -> Result<Vec<NoteId>, ClientError> {
+        client.sync_state().await?;
+        let new_notes = client
+            .get_consumable_notes(self.account_id)
+            .await?
+            .into_iter()
+            .map(|(record, _)| record.id())
+            .filter(|note_id| self.seen.insert(*note_id))
+            .collect();
+        Ok(new_notes)
+    }
+}
+
+/// How often `watch_account` syncs with the node while waiting for a state change.
+pub const ACCOUNT_WATCH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Returns a stream of snapshots of an account, yielding one each time its state changes.
+///
+/// The first poll records the current state without yielding it; afterwards the stream syncs
+/// every `ACCOUNT_WATCH_INTERVAL`, takes a `snapshot_account` and yields it if it differs from
+/// the last recorded one. Snapshots are compared as a whole, so a change of the nonce, a storage
+/// slot or a balance is yielded once, and polls that see the same state yield nothing. Polling
+/// only happens while the stream is being awaited, and changes that are undone between two polls
+/// are not seen.
+///
+/// The account must be tracked by the client. Changes made by other clients to a public account
+/// are picked up by the sync. The stream ends if syncing with the node or reading the store
+/// fails. To stop watching earlier, drop the stream or bound it with a combinator such as
+/// `take` or `take_until`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account to watch.
+///
+/// # Returns
+///
+/// Returns a stream of the account's snapshots after each change.
+pub fn watch_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+) -> impl Stream<Item = AccountSnapshot> + '_ {
+    let state = (client, None::<AccountSnapshot>);
+
+    stream::unfold(state, move |(client, mut last)| async move {
+        loop {
+            if last.is_some() {
+                sleep(ACCOUNT_WATCH_INTERVAL).await;
+            }
+
+            client.sync_state().await.ok()?;
+            let snapshot = snapshot_account(client, account_id).await.ok()?;
+
+            match last {
+                Some(ref previous) if *previous != snapshot => {
+                    return Some((snapshot.clone(), (client, Some(snapshot))));
+                }
+                Some(_) => {}
+                None => last = Some(snapshot),
+            }
+        }
+    })
+}
+
+/// The outcome of a sync-dependent operation of a `SyncController`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncStatus<T> {
+    /// Syncing is enabled and the operation completed.
+    Done(T),
+    /// Syncing is paused, so the operation was skipped without contacting the node.
+    Paused,
+}
+
+/// Wraps a client and lets syncing be paused and resumed, e.g. to cut RPC load during long
+/// interactive sessions.
+///
+/// While paused, `sync_state` and `wait_for_note` return `SyncStatus::Paused` right away instead
+/// of contacting the node. Other operations are unaffected; reach them through `client`. Note
+/// that the free helpers of this crate, such as the free `wait_for_note`, sync the client they
+/// are given directly and do not know about the controller: call them on `client()` only while
+/// syncing is resumed, or use the controller's own methods.
+pub struct SyncController<AUTH> {
+    client: Client<AUTH>,
+    paused: bool,
+}
+
+impl<AUTH: TransactionAuthenticator + Sync + 'static> SyncController<AUTH> {
+    /// Wraps `client`; syncing starts enabled.
+    pub fn new(client: Client<AUTH>) -> Self {
+        Self {
+            client,
+            paused: false,
+        }
+    }
+
+    /// Stops syncing until `resume` is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Enables syncing again.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns `true` if syncing is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&mut self) -> &mut Client<AUTH> {
+        &mut self.client
+    }
+
+    /// Unwraps the controller, returning the client.
+    pub fn into_client(self) -> Client<AUTH> {
+        self.client
+    }
+
+    /// Syncs the client with the node, unless syncing is paused.
+    pub async fn sync_state(&mut self) -> Result<SyncStatus<SyncSummary>, ClientError> {
+        if self.paused {
+            return Ok(SyncStatus::Paused);
+        }
+        Ok(SyncStatus::Done(self.client.sync_state().await?))
+    }
+
+    /// Waits for a note to be committed like the free `wait_for_note`, unless syncing is paused.
+    ///
+    /// The paused state is checked once, before waiting: the controller is borrowed for the
+    /// whole wait, so it cannot be paused while the wait is in progress.
+    pub async fn wait_for_note(&mut self, expected: &Note) -> Result<SyncStatus<()>, ClientError> {
+        if self.paused {
+            return Ok(SyncStatus::Paused);
+        }
+        wait_for_note(&mut self.client, expected).await?;
+        Ok(SyncStatus::Done(()))
+    }
+}
+
+/// Describes one output note of a transaction built with `build_multi_output_request`.
+#[derive(Debug, Clone)]
+pub struct OutputNoteSpec {
+    /// The assets carried by the note.
+    pub assets: Vec<Asset>,
+    /// The recipient of the note.
+    pub recipient: NoteRecipient,
+    /// Whether the note is public or private.
+    pub note_type: NoteType,
+    /// The tag of the note. Public use-case tags are only valid for public notes.
+    pub tag: NoteTag,
+}
+
+/// The parts of a transaction request built with `build_full_request`.
+#[derive(Debug, Clone, Default)]
+pub struct FullTxSpec {
+    /// Committed notes to consume, by ID; the client must track them with an inclusion proof.
+    pub authenticated_inputs: Vec<NoteId>,
+    /// Notes to consume that are passed in full and need not be committed yet.
+    pub unauthenticated_inputs: Vec<Note>,
+    /// A custom transaction script. It cannot be combined with `own_outputs`, since the
+    /// script is then responsible for creating the output notes itself.
+    pub script: Option<TransactionScript>,
+    /// Notes created by the executing account, through a script generated by the client.
+    pub own_outputs: Vec<Note>,
+}
+
+/// Builds a transaction request from input notes, an optional script and output notes.
+///
+/// # Arguments
+///
+/// * `spec` - The inputs, script and outputs of the transaction.
+///
+/// # Returns
+///
+/// Returns the `TransactionRequest`, or an error if the builder rejects the combination, e.g.
+/// a custom script together with own output notes.
+pub fn build_full_request(spec: FullTxSpec) -> Result<TransactionRequest, ToolsError> {
+    let mut builder = TransactionRequestBuilder::new()
+        .authenticated_input_notes(spec.authenticated_inputs.into_iter().map(|id| (id, None)))
+        .unauthenticated_input_notes(spec.unauthenticated_inputs.into_iter().map(|n| (n, None)));
+
+    if let Some(script) = spec.script {
+        builder = builder.custom_script(script);
+    }
+    if !spec.own_outputs.is_empty() {
+        builder = builder.own_output_notes(spec.own_outputs.into_iter().map(OutputNote::Full));
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Returns the notes of one type created by an executed transaction.
+///
+/// Only notes whose full details are part of the transaction result are returned; output notes
+/// known by header or partial details only, e.g. private notes created by a note script, are
+/// skipped since they cannot be turned into a `Note`.
+///
+/// # Arguments
+///
+/// * `tx_result` - The executed transaction.
+/// * `note_type` - The type of the notes to return.
+///
+/// # Returns
+///
+/// Returns the matching notes, in creation order.
+pub fn created_notes_of_type(tx_result: &TransactionResult, note_type: NoteType) -> Vec<Note> {
+    tx_result
+        .created_notes()
+        .iter()
+        .filter(|output_note| output_note.metadata().note_type() == note_type)
+        .filter_map(|output_note| match output_note {
+            OutputNote::Full(note) => Some(note.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds a transaction request creating several output notes, possibly of different types.
+///
+/// # Arguments
+///
+/// * `sender` - The account that will execute the transaction and send the notes.
+/// * `outputs` - The notes to create.
+///
+/// # Returns
+///
+/// Returns a `TransactionRequest` with one own output note per spec, or an error if `outputs`
+/// is empty or the combined amount of a fungible asset does not fit in a single asset.
+pub fn build_multi_output_request(
+    sender: AccountId,
+    outputs: Vec<OutputNoteSpec>,
+) -> Result<TransactionRequest, ToolsError> {
+    if outputs.is_empty() {
+        return Err(ToolsError::NoOutputs);
+    }
+
+    let mut totals: HashMap<AccountId, u64> = HashMap::new();
+    let mut notes = Vec::with_capacity(outputs.len());
+    for spec in outputs {
+        for asset in &spec.assets {
+            if let Asset::Fungible(fungible) = asset {
+                let total = totals.entry(fungible.faucet_id()).or_default();
+                *total = total
+                    .checked_add(fungible.amount())
+                    .filter(|total| *total <= FungibleAsset::MAX_AMOUNT)
+                    .ok_or(ToolsError::AmountOverflow(fungible.faucet_id()))?;
+            }
+        }
+
+        let metadata = NoteMetadata::new(
+            sender,
+            spec.note_type,
+            spec.tag,
+            NoteExecutionHint::always(),
+            Felt::new(0),
+        )?;
+        let vault = safe_note_assets(spec.assets)?;
+        notes.push(OutputNote::Full(Note::new(vault, metadata, spec.recipient)));
+    }
+
+    Ok(TransactionRequestBuilder::new()
+        .own_output_notes(notes)
+        .build()?)
+}
+
+/// Serializes a transaction request, e.g. to attach it to a bug report or store it as a fixture.
+///
+/// Input and output notes (including their serial numbers), scripts and advice data are all
+/// part of the serialized request.
+pub fn serialize_tx_request(tx_request: &TransactionRequest) -> Vec<u8> {
+    tx_request.to_bytes()
+}
+
+/// Deserializes a transaction request produced by `serialize_tx_request`.
+pub fn deserialize_tx_request(bytes: &[u8]) -> Result<TransactionRequest, DeserializationError> {
+    TransactionRequest::read_from_bytes(bytes)
+}
+
+/// Proves an executed transaction locally and writes the proof to a file.
+///
+/// The client proves transactions inside `submit_transaction` and does not keep the proof, so
+/// the executed transaction is proven again here. The proof covers the same execution, i.e.
+/// the same inputs, outputs and account delta, so it attests exactly what the client executed
+/// and can be checked without a node using `verify_transaction_proof`.
+///
+/// # Arguments
+///
+/// * `tx` - The executed transaction, e.g. as returned by `new_transaction`.
+/// * `path` - The file to write the serialized `ProvenTransaction` to.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the proof is written.
+pub async fn export_transaction_proof(
+    tx: &TransactionResult,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(), ToolsError> {
+    let prover = LocalTransactionProver::new(ProvingOptions::default());
+    let proven_tx = prover
+        .prove(tx.executed_transaction().clone().into())
+        .await?;
+    std::fs::write(path, proven_tx.to_bytes())?;
+    Ok(())
+}
+
+/// Reads a proof written by `export_transaction_proof` and verifies it against the kernel.
+///
+/// The proof is checked at `MIN_PROOF_SECURITY_LEVEL`, the level the node requires.
+///
+/// # Arguments
+///
+/// * `path` - The file holding the serialized `ProvenTransaction`.
+///
+/// # Returns
+///
+/// Returns the ID of the proven transaction, or `ToolsError::Verification` if the proof is
+/// invalid.
+pub fn verify_transaction_proof(
+    path: impl AsRef<std::path::Path>,
+) -> Result<TransactionId, ToolsError> {
+    let bytes = std::fs::read(path)?;
+    let proven_tx = ProvenTransaction::read_from_bytes(&bytes)?;
+    TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL).verify(&proven_tx)?;
+    Ok(proven_tx.id())
+}
+
+/// Deserializes a transaction request and executes and submits it against an account.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account executing the transaction.
+/// * `bytes` - A request serialized with `serialize_tx_request`.
+///
+/// # Returns
+///
+/// Returns the submitted `TransactionResult`.
+pub async fn replay_request<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    bytes: &[u8],
+) -> Result<TransactionResult, ToolsError> {
+    let tx_request = deserialize_tx_request(bytes)?;
+    let tx_result = client.new_transaction(account_id, tx_request).await?;
+    submit_transaction_with_retry(
+        client,
+        tx_result.clone(),
+        SUBMIT_RETRIES,
+        SUBMIT_RETRY_DELAY,
+    )
+    .await?;
+    client.sync_state().await?;
+    Ok(tx_result)
+}
+
+/// The maximum number of auth keys an account can recognize.
+///
+/// An account has exactly one auth component, and `AuthRpoFalcon512` checks signatures against a
+/// single public key. To move an account to a new key, create it with `create_rotatable_account`
+/// and call `rotate_auth_key`.
+pub const MAX_AUTH_KEYS: usize = 1;
+
+/// Creates a basic wallet account authenticated by the given Falcon keys.
+///
+/// Exactly one key is supported, see `MAX_AUTH_KEYS`; transactions signed by any other key are
+/// rejected.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the account's secret key.
+/// * `keys` - The keys that should be able to sign for the account; must hold exactly one key.
+///
+/// # Returns
+///
+/// Returns the created `Account`, or `ToolsError::UnsupportedAuthKeys` if `keys` does not hold
+/// exactly one key.
+pub async fn create_account_with_auth_keys<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    keys: &[SecretKey],
+) -> Result<Account, ToolsError> {
+    let [key_pair] = keys else {
+        return Err(ToolsError::UnsupportedAuthKeys {
+            requested: keys.len(),
+            max: MAX_AUTH_KEYS,
+        });
+    };
+
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let builder = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key()))
+        .with_component(BasicWallet);
+
+    let (account, seed) = builder.build().map_err(ClientError::from)?;
+    client.add_account(&account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
+        .map_err(ToolsError::KeyStore)?;
+
+    Ok(account)
+}
+
+/// The library path under which `rotatable_auth_component` is linked into scripts.
+const ROTATABLE_AUTH_LIBRARY_PATH: &str = "auth::rotatable_rpo_falcon512";
+
+/// Builds an RPO Falcon 512 auth component whose key can be rotated with `rotate_auth_key`.
+///
+/// The component is `masm/auth/rotatable_rpo_falcon512.masm`: it authenticates transactions
+/// exactly like `AuthRpoFalcon512`, with the public key in storage slot 0, and keeps a staged
+/// replacement key in slot 1.
+///
+/// # Arguments
+///
+/// * `public_key` - The initial public key.
+///
+/// # Returns
+///
+/// Returns the compiled `AccountComponent`.
+pub fn rotatable_auth_component(public_key: PublicKey) -> Result<AccountComponent, AccountError> {
+    compile_account_component(
+        include_str!("../masm/auth/rotatable_rpo_falcon512.masm").to_string(),
+        vec![
+            StorageSlot::Value(public_key.into()),
+            StorageSlot::empty_value(),
+        ],
+    )
+}
+
+/// Creates a basic wallet account whose auth key can be rotated and adds it to the client.
+///
+/// Like `create_basic_account`, but authenticating through `rotatable_auth_component`.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client to interact with.
+/// * `keystore` - The keystore to store the account's secret key.
+///
+/// # Returns
+///
+/// Returns a tuple containing the created `Account` and the associated `SecretKey`.
+pub async fn create_rotatable_account<
+    AUTH: TransactionAuthenticator + Sync + 'static,
+    K: KeyStore,
+>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+) -> Result<(Account, SecretKey), ClientError> {
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let key_pair = SecretKey::with_rng(client.rng());
+    let builder = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(rotatable_auth_component(key_pair.public_key())?)
+        .with_component(BasicWallet);
+
+    let (account, seed) = builder.build()?;
+    client.add_account(&account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
+        .unwrap();
+
+    Ok((account, key_pair))
+}
+
+/// Replaces the auth key of an account created with `create_rotatable_account`.
+///
+/// The rotation takes two transactions: the first, signed with the current key, stages the new
+/// public key; the second activates it and is already signed with the new key. The new key is
+/// added to `keystore` once the first transaction has been submitted, so a failed first
+/// transaction leaves the keystore untouched; the old key is left in place.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `keystore` - The keystore to store the new secret key, usually the client's authenticator.
+/// * `account` - The account whose key to rotate.
+/// * `new_key` - The new secret key.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the new key is active, `ToolsError::ImmutableAccountCode` if the
+/// account does not have updatable code, or `ToolsError::MissingProcedure` if its code does not
+/// contain the procedures of `rotatable_auth_component`.
+pub async fn rotate_auth_key<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
+    account: &Account,
+    new_key: SecretKey,
+) -> Result<(), ToolsError> {
+    if account.account_type() != AccountType::RegularAccountUpdatableCode {
+        return Err(ToolsError::ImmutableAccountCode(account.id()));
+    }
+
+    let component =
+        rotatable_auth_component(new_key.public_key()).map_err(ClientError::AccountError)?;
+    if let Some((root, _)) = component
+        .get_procedures()
+        .into_iter()
+        .find(|(root, _)| !account.code().has_procedure(*root))
+    {
+        return Err(ToolsError::MissingProcedure {
+            account_id: account.id(),
+            root,
+        });
+    }
+
+    let library = create_library(
+        include_str!("../masm/auth/rotatable_rpo_falcon512.masm").to_string(),
+        ROTATABLE_AUTH_LIBRARY_PATH,
+    )
+    .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
+
+    let [e0, e1, e2, e3]: [Felt; 4] = Word::from(new_key.public_key()).into();
+    let scripts = [
+        format!(
+            "use.{ROTATABLE_AUTH_LIBRARY_PATH}
+
+            begin
+                push.{e0}.{e1}.{e2}.{e3}
+                call.rotatable_rpo_falcon512::set_pending_auth_key
+                dropw
+            end"
+        ),
+        format!(
+            "use.{ROTATABLE_AUTH_LIBRARY_PATH}
+
+            begin
+                call.rotatable_rpo_falcon512::activate_pending_auth_key
+            end"
+        ),
+    ];
+
+    for (i, script_code) in scripts.into_iter().enumerate() {
+        let tx_script = compile_tx_script(&script_code, Some(&library))?;
+        let tx_req = TransactionRequestBuilder::new()
+            .custom_script(tx_script)
+            .build()?;
+        let tx_exec = client.new_transaction(account.id(), tx_req).await?;
+        submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+        client.sync_state().await?;
+
+        // the activation is signed with the new key, which is only stored once it is staged
+        if i == 0 {
+            keystore
+                .add_key(&AuthSecretKey::RpoFalcon512(new_key.clone()))
+                .map_err(ToolsError::KeyStore)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The network an RPC endpoint belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    /// A node running on the local machine.
+    Localhost,
+    /// The public Miden devnet.
+    Devnet,
+    /// The public Miden testnet.
+    Testnet,
+    /// Any other node.
+    Custom,
+}
+
+impl Network {
+    /// Returns the canonical RPC endpoint of the network, or `None` for `Network::Custom`.
+    ///
+    /// Localhost uses the node's default port; devnet and testnet use the endpoints provided by
+    /// `miden-client`.
+    pub fn endpoint(&self) -> Option<Endpoint> {
+        match self {
+            Network::Localhost => Some(Endpoint::localhost()),
+            Network::Devnet => Some(Endpoint::devnet()),
+            Network::Testnet => Some(Endpoint::testnet()),
+            Network::Custom => None,
+        }
+    }
+}
+
+/// Helper to instantiate a `Client` connected to a well-known network.
+///
+/// Like `instantiate_client` with the endpoint from `Network::endpoint`; use `instantiate_client`
+/// directly for custom nodes. Building the client does not contact the node.
+///
+/// # Arguments
+///
+/// * `network` - The network to connect to.
+/// * `store_path` - An optional path to the SQLite store.
+///
+/// # Returns
+///
+/// Returns the `Client`, or `ToolsError::NoCanonicalEndpoint` if `Network::endpoint` returns
+/// `None`.
+pub async fn instantiate_client_for(
+    network: Network,
+    store_path: Option<&str>,
+) -> Result<Client, ToolsError> {
+    let endpoint = network
+        .endpoint()
+        .ok_or(ToolsError::NoCanonicalEndpoint(network))?;
+    Ok(instantiate_client(endpoint, store_path).await?)
+}
+
+/// Classifies an endpoint by its host.
+///
+/// Scripts can use this as a guardrail, e.g. to refuse destructive operations on anything but
+/// `Network::Localhost`. Hosts that are not known Miden endpoints are `Network::Custom`.
+pub fn classify_endpoint(endpoint: &Endpoint) -> Network {
+    match endpoint.host() {
+        "localhost" | "127.0.0.1" | "0.0.0.0" | "::1" => Network::Localhost,
+        host if host == Endpoint::devnet().host() => Network::Devnet,
+        host if host == Endpoint::testnet().host() => Network::Testnet,
+        _ => Network::Custom,
+    }
+}
+
+/// Mints fungible tokens from a faucet into a note with an arbitrary recipient.
+///
+/// Unlike `build_mint_fungible_asset`, the recipient is not required to be a P2ID recipient, so
+/// the minted note can be consumable by any note script.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `faucet` - The faucet to mint tokens from.
+/// * `recipient` - The recipient of the minted note.
+/// * `amount` - The number of tokens to mint.
+/// * `note_type` - The type of the minted note.
+///
+/// # Returns
+///
+/// Returns the minted `Note`.
+pub async fn mint_to_recipient<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    faucet: &Account,
+    recipient: NoteRecipient,
+    amount: u64,
+    note_type: NoteType,
+) -> Result<Note, ToolsError> {
+    let asset = FungibleAsset::new(faucet.id(), amount).map_err(ClientError::AssetError)?;
+    let tag = match note_type {
+        NoteType::Public => public_tag(0, 0, NoteExecutionMode::Local)?,
+        _ => NoteTag::for_local_use_case(0, 0)?,
+    };
+    let metadata = NoteMetadata::new(
+        faucet.id(),
+        note_type,
+        tag,
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )?;
+    let note = Note::new(NoteAssets::new(vec![asset.into()])?, metadata, recipient);
+
+    let mint_req = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()?;
+    let tx_exec = client.new_transaction(faucet.id(), mint_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    Ok(note)
+}
+
+/// Mints from one faucet to several targets, one transaction at a time.
+///
+/// Each mint is submitted only after the previous one is committed and the client has synced,
+/// so every transaction is executed against the faucet's latest committed state and nonce.
+/// Firing the mints back to back instead lets the node reject those built on a stale nonce.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `faucet` - The faucet to mint tokens from.
+/// * `targets` - Pairs of target account and amount, minted in order.
+/// * `note_type` - The type of the minted P2ID notes.
+///
+/// # Returns
+///
+/// Returns the minted notes, in the order of `targets`, or `ToolsError::PartialOutputNote` if a
+/// mint transaction does not output its note in full.
+pub async fn mint_series<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    faucet: &Account,
+    targets: &[(AccountId, u64)],
+    note_type: NoteType,
+) -> Result<Vec<Note>, ToolsError> {
+    let mut notes = Vec::with_capacity(targets.len());
+    for &(target, amount) in targets {
+        client.sync_state().await?;
+
+        let asset = FungibleAsset::new(faucet.id(), amount).map_err(ClientError::AssetError)?;
+        let mint_req = TransactionRequestBuilder::new().build_mint_fungible_asset(
+            asset,
+            target,
+            note_type,
+            client.rng(),
+        )?;
+        let tx_exec = client.new_transaction(faucet.id(), mint_req).await?;
+        submit_transaction_with_retry(client, tx_exec.clone(), SUBMIT_RETRIES, SUBMIT_RETRY_DELAY)
+            .await?;
+
+        let note = match tx_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note,
+            output_note => return Err(ToolsError::PartialOutputNote(output_note.id())),
+        };
+        wait_for_note(client, note).await?;
+        notes.push(note.clone());
+    }
+
+    Ok(notes)
+}
+
+/// Opens a store database, failing if the file does not exist.
+///
+/// The busy timeout is disabled, so any statement that needs a lock held by another connection
+/// (such as a live client) fails at once instead of waiting for it.
+fn open_store(store_path: &str) -> Result<rusqlite::Connection, std::io::Error> {
+    if !std::path::Path::new(store_path).exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("store not found: {store_path}"),
+        ));
+    }
+
+    let connection =
+        rusqlite::Connection::open(store_path).map_err(|err| store_io_error(store_path, err))?;
+    connection
+        .busy_timeout(Duration::ZERO)
+        .map_err(|err| store_io_error(store_path, err))?;
+    Ok(connection)
+}
+
+/// Converts a SQLite error into an I/O error, reporting a locked store as `WouldBlock`.
+fn store_io_error(store_path: &str, err: rusqlite::Error) -> std::io::Error {
+    match err.sqlite_error_code() {
+        Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) => {
+            std::io::Error::new(
+                std::io::ErrorKind::WouldBlock,
+                format!("store {store_path} is in use; close the client first"),
+            )
+        }
+        _ => std::io::Error::other(err),
+    }
+}
+
+/// Starts an exclusive transaction on a store opened with `open_store`.
+///
+/// The store, and any database attached to the connection, stays locked against other
+/// connections until the transaction is committed or dropped. SQLite cannot tell whether another
+/// connection merely has the store open, so an idle client goes unnoticed; a client that is
+/// reading or writing makes this fail with `WouldBlock`, and one that tries to while the lock is
+/// held gets a busy error instead of seeing a half-finished operation.
+fn lock_store<'a>(
+    connection: &'a rusqlite::Connection,
+    store_path: &str,
+) -> Result<rusqlite::Transaction<'a>, std::io::Error> {
+    rusqlite::Transaction::new_unchecked(connection, rusqlite::TransactionBehavior::Exclusive)
+        .map_err(|err| store_io_error(store_path, err))
+}
+
+/// Runs `VACUUM` on a SQLite store, reclaiming the space left by deleted rows.
+///
+/// The client using the store must be dropped first: if another connection holds a lock on the
+/// database, this returns an error of kind `WouldBlock` instead of waiting for it. `VACUUM`
+/// takes an exclusive lock for its whole run, so no client can use the store in the meantime.
+///
+/// # Arguments
+///
+/// * `store_path` - The path to the SQLite store.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the store has been compacted.
+pub fn compact_store(store_path: &str) -> Result<(), std::io::Error> {
+    let connection = open_store(store_path)?;
+    connection
+        .execute_batch("VACUUM;")
+        .map_err(|err| store_io_error(store_path, err))?;
+    tracing::info!(store_path, "compacted sqlite store");
+    Ok(())
+}
+
+/// Runs SQLite's `PRAGMA integrity_check` on a store.
+///
+/// Like `compact_store`, this fails with an error of kind `WouldBlock` if a client is using the
+/// store.
+///
+/// # Arguments
+///
+/// * `store_path` - The path to the SQLite store.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the check passes, or `ToolsError::CorruptStore` with the problems SQLite
+/// found.
+pub fn check_store_integrity(store_path: &str) -> Result<(), ToolsError> {
+    let connection = open_store(store_path)?;
+    let tx = lock_store(&connection, store_path)?;
+    let problems = tx
+        .prepare("PRAGMA integrity_check")
+        .and_then(|mut statement| {
+            statement
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(std::io::Error::other)?;
+
+    if problems.iter().any(|problem| problem != "ok") {
+        return Err(ToolsError::CorruptStore {
+            path: store_path.to_string(),
+            problems,
+        });
+    }
+    Ok(())
+}
+
+/// The store tables whose rows `merge_stores` copies, parents before the tables referencing them.
+///
+/// These hold the accounts (code, storage, storage maps, vault assets) and the notes (scripts,
+/// input and output notes); sync state, block headers and tags are specific to each store and
+/// are left alone.
+const MERGED_TABLES: &[&str] = &[
+    "account_code",
+    "account_storage",
+    "storage_map_entries",
+    "account_assets",
+    "accounts",
+    "notes_scripts",
+    "input_notes",
+    "output_notes",
+];
+
+/// Detaches the `source` database from a connection when dropped, so that a failed merge does
+/// not leave it attached for the next source.
+struct AttachedSource<'a>(&'a rusqlite::Connection);
+
+impl Drop for AttachedSource<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.execute("DETACH DATABASE source", []);
+    }
+}
+
+/// Returns the column names of a table, or an empty list if the table does not exist.
+fn table_columns(
+    connection: &rusqlite::Connection,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<String>, rusqlite::Error> {
+    connection
+        .prepare(&format!("PRAGMA {schema}.table_info({table})"))?
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect()
+}
+
+/// Returns the schema version of a database, as recorded by the client's migrations.
+fn schema_version(connection: &rusqlite::Connection, schema: &str) -> Result<i64, rusqlite::Error> {
+    connection.query_row(&format!("PRAGMA {schema}.user_version"), [], |row| {
+        row.get(0)
+    })
+}
+
+/// Returns the `CREATE TABLE` statement of a table, or `None` if the table does not exist.
+fn table_sql(
+    connection: &rusqlite::Connection,
+    schema: &str,
+    table: &str,
+) -> Result<Option<String>, rusqlite::Error> {
+    connection
+        .prepare(&format!(
+            "SELECT sql FROM {schema}.sqlite_master WHERE type = 'table' AND name = ?1"
+        ))?
+        .query_map([table], |row| row.get(0))?
+        .next()
+        .transpose()
+}
+
+/// Copies the accounts and notes of several SQLite stores into a target store.
+///
+/// Every source must have the same schema version as the target, and every table in
+/// `MERGED_TABLES` must be defined identically in both; otherwise the merge stops with
+/// `ToolsError::StoreSchemaMismatch`. Rows present in both stores are skipped, so merging the
+/// same source twice is a no-op, but a source row whose primary key (e.g. an account ID and
+/// nonce, or a note ID) already exists in the target with different contents stops the merge
+/// with `ToolsError::StoreMergeConflict`. Only the tables in `MERGED_TABLES` are copied; a table
+/// missing from both stores is skipped. No client may be using the stores.
+///
+/// # Arguments
+///
+/// * `target_path` - The path to the store receiving the rows; it must already exist.
+/// * `sources` - The paths to the stores to copy from.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once every source has been merged, `ToolsError::StoreSchemaMismatch` or
+/// `ToolsError::StoreMergeConflict` as described above, or `ToolsError::Io` if a store is
+/// missing, in use, or cannot be read. A source that fails to merge leaves the target as it was
+/// before that source.
+pub fn merge_stores(target_path: &str, sources: &[&str]) -> Result<(), ToolsError> {
+    let connection = open_store(target_path)?;
+
+    for source in sources {
+        drop(open_store(source)?);
+
+        connection
+            .execute("ATTACH DATABASE ?1 AS source", [source])
+            .map_err(std::io::Error::other)?;
+        let _attached = AttachedSource(&connection);
+        // locks the target and the attached source until the merge is committed
+        let tx = lock_store(&connection, target_path)?;
+
+        let schema_mismatch = || ToolsError::StoreSchemaMismatch {
+            source: source.to_string(),
+        };
+        if schema_version(&tx, "main").map_err(std::io::Error::other)?
+            != schema_version(&tx, "source").map_err(std::io::Error::other)?
+        {
+            return Err(schema_mismatch());
+        }
+
+        for table in MERGED_TABLES {
+            let target_sql = table_sql(&tx, "main", table).map_err(std::io::Error::other)?;
+            if target_sql != table_sql(&tx, "source", table).map_err(std::io::Error::other)? {
+                return Err(schema_mismatch());
+            }
+            if target_sql.is_none() {
+                continue;
+            }
+
+            // rows already in the target are skipped; a different row with the same primary
+            // key violates its uniqueness constraint
+            let columns = table_columns(&tx, "main", table)
+                .map_err(std::io::Error::other)?
+                .join(", ");
+            let sql = format!(
+                "INSERT INTO main.{table} ({columns}) \
+                 SELECT {columns} FROM source.{table} EXCEPT SELECT {columns} FROM main.{table}"
+            );
+            match tx.execute(&sql, []) {
+                Ok(_) => {}
+                Err(err)
+                    if err.sqlite_error_code()
+                        == Some(rusqlite::ErrorCode::ConstraintViolation) =>
+                {
+                    return Err(ToolsError::StoreMergeConflict {
+                        source: source.to_string(),
+                        table: table.to_string(),
+                    });
+                }
+                Err(err) => return Err(std::io::Error::other(err).into()),
+            }
+        }
+        tx.commit()
+            .map_err(|err| store_io_error(target_path, err))?;
+        tracing::info!(source, target_path, "merged sqlite store");
+    }
+
+    Ok(())
+}
+
+/// Builds a tag for a public note use case.
+///
+/// Public use-case tags group notes by an application-defined `use_case` (14 bits) and
+/// `payload`, and are only valid on public notes. `create_public_note` uses
+/// `public_tag(0, 0, NoteExecutionMode::Local)`.
+///
+/// # Arguments
+///
+/// * `use_case` - The use case identifier; must fit in 14 bits.
+/// * `payload` - Use-case specific data.
+/// * `mode` - Whether the note is meant to be executed locally or by the network.
+///
+/// # Returns
+///
+/// Returns the `NoteTag`, or a `NoteError` if the use case does not fit.
+pub fn public_tag(
+    use_case: u16,
+    payload: u16,
+    mode: NoteExecutionMode,
+) -> Result<NoteTag, NoteError> {
+    NoteTag::for_public_use_case(use_case, payload, mode)
+}
+
+/// Builds the tag that routes a note to an account, as used by P2ID notes.
+pub fn account_tag(account_id: AccountId) -> NoteTag {
+    NoteTag::from_account_id(account_id)
+}
+
+/// A single mint planned by `plan_setup`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedMint {
+    /// Index of the receiving account.
+    pub account_index: usize,
+    /// Index of the minting faucet.
+    pub faucet_index: usize,
+    /// Number of tokens to mint.
+    pub amount: u64,
+}
+
+/// What `setup_accounts_and_faucets` would do for a given input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupPlan {
+    /// Number of accounts that would be created.
+    pub num_accounts: usize,
+    /// Number of faucets that would be created.
+    pub num_faucets: usize,
+    /// The mints that would be performed, in execution order.
+    pub mints: Vec<PlannedMint>,
+    /// Total tokens minted per faucet, indexed like the faucets.
+    pub tokens_per_faucet: Vec<u64>,
+    /// Number of transactions submitted: one mint and one consume per planned mint.
+    pub num_transactions: usize,
+}
+
+/// Plans a `setup_accounts_and_faucets` run without executing any transaction.
+///
+/// # Arguments
+///
+/// * `num_accounts` - The number of accounts to create.
+/// * `num_faucets` - The number of faucets to create.
+/// * `balances` - A matrix where each entry represents the number of tokens to mint from a faucet to an account.
+///
+/// # Returns
+///
+/// Returns the `SetupPlan`, or `ToolsError::InvalidBalanceMatrix` if `balances` is not a
+/// `num_accounts` x `num_faucets` matrix or the total for a faucet does not fit in a `u64`.
+pub fn plan_setup(
+    num_accounts: usize,
+    num_faucets: usize,
+    balances: &[Vec<u64>],
+) -> Result<SetupPlan, ToolsError> {
+    if balances.len() != num_accounts || balances.iter().any(|row| row.len() != num_faucets) {
+        return Err(ToolsError::InvalidBalanceMatrix {
+            accounts: num_accounts,
+            faucets: num_faucets,
+        });
+    }
+
+    let mut mints = Vec::new();
+    let mut tokens_per_faucet = vec![0; num_faucets];
+    for (account_index, row) in balances.iter().enumerate() {
+        for (faucet_index, &amount) in row.iter().enumerate() {
+            if amount == 0 {
+                continue;
+            }
+            let total = &mut tokens_per_faucet[faucet_index];
+            *total = total
+                .checked_add(amount)
+                .ok_or(ToolsError::InvalidBalanceMatrix {
+                    accounts: num_accounts,
+                    faucets: num_faucets,
+                })?;
+            mints.push(PlannedMint {
+                account_index,
+                faucet_index,
+                amount,
+            });
+        }
+    }
+
+    Ok(SetupPlan {
+        num_accounts,
+        num_faucets,
+        num_transactions: mints.len() * 2,
+        mints,
+        tokens_per_faucet,
+    })
+}
+
+/// Consumes a note using a caller-provided inclusion proof instead of one from the store.
+///
+/// The proof is checked against the note root of the block it points to before the note is
+/// imported with the proof and consumed as an authenticated input note.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account that will consume the note.
+/// * `note` - The note to consume.
+/// * `proof` - The inclusion proof of the note.
+///
+/// # Returns
+///
+/// Returns the submitted `TransactionResult`, or `ToolsError::InvalidInclusionProof` if the proof
+/// does not prove the note.
+#[cfg(feature = "testing")]
+pub async fn consume_with_proof<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    note: Note,
+    proof: NoteInclusionProof,
+) -> Result<TransactionResult, ToolsError> {
+    let (block_header, _) = client
+        .test_rpc_api()
+        .get_block_header_by_number(Some(proof.location().block_num()), false)
+        .await
+        .map_err(ClientError::from)?;
+
+    let proven_root = proof.note_path().compute_root(
+        proof.location().node_index_in_block().into(),
+        note.commitment(),
+    );
+    if proven_root.ok() != Some(block_header.note_root()) {
+        return Err(ToolsError::InvalidInclusionProof(note.id()));
+    }
+
+    let note_id = client
+        .import_note(NoteFile::NoteWithProof(note, proof))
+        .await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(note_id, None)])
+        .build()?;
+    let tx_result = client.new_transaction(account.id(), consume_req).await?;
+    submit_transaction_with_retry(
+        client,
+        tx_result.clone(),
+        SUBMIT_RETRIES,
+        SUBMIT_RETRY_DELAY,
+    )
+    .await?;
+    client.sync_state().await?;
+
+    Ok(tx_result)
+}
+
+/// Creates a swap note offering one asset in exchange for a basket of assets.
+///
+/// The note runs `masm/notes/MULTI_SWAP.masm`. When an account consumes it, the script creates a
+/// single payback note carrying every requested asset, taken from the consuming account's vault,
+/// and only then adds the offered asset to that vault; if any requested asset is missing, the
+/// consume transaction fails. The payback note is a P2ID note to `maker` of the same `note_type`,
+/// tagged with `account_tag(maker.id())`, whose serial number is drawn when the swap is created.
+/// Its details are returned so the maker can recognize, import and consume it.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `maker` - The account offering the asset; it must hold `offered`.
+/// * `offered` - The asset carried by the swap note.
+/// * `requested` - The assets the taker must pay; at most one per faucet.
+/// * `note_type` - The type of the swap note and of the payback note.
+///
+/// # Returns
+///
+/// Returns the submitted swap `Note` and the `NoteDetails` of the payback note, or
+/// `ToolsError::NoRequestedAssets` / `ToolsError::DuplicateRequestedFaucet` if the requested
+/// basket is invalid.
+pub async fn create_multi_asset_swap<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    maker: &Account,
+    offered: Asset,
+    requested: Vec<Asset>,
+    note_type: NoteType,
+) -> Result<(Note, NoteDetails), ToolsError> {
+    if requested.is_empty() {
+        return Err(ToolsError::NoRequestedAssets);
+    }
+    let mut faucet_ids = HashSet::new();
+    for asset in &requested {
+        if !faucet_ids.insert(asset.faucet_id_prefix()) {
+            return Err(ToolsError::DuplicateRequestedFaucet(
+                asset.faucet_id_prefix(),
+            ));
+        }
+    }
+
+    let payback_serial_num = client.rng().draw_word();
+    let payback_recipient = utils::build_p2id_recipient(maker.id(), payback_serial_num)?;
+    let payback_tag = account_tag(maker.id());
+
+    let mut inputs: Vec<Felt> = <[Felt; 4]>::from(payback_recipient.digest()).to_vec();
+    // stored so that loading the word yields `[tag, aux, note_type, execution_hint]`
+    inputs.extend([
+        NoteExecutionHint::always().into(),
+        note_type.into(),
+        Felt::new(0),
+        payback_tag.into(),
+    ]);
+    for asset in &requested {
+        inputs.extend(<[Felt; 4]>::from(Word::from(*asset)));
+    }
+
+    let note_script = compile_note_script(include_str!("../masm/notes/MULTI_SWAP.masm"), None)?;
+    let serial_num = client.rng().draw_word();
+    let recipient = NoteRecipient::new(serial_num, note_script, NoteInputs::new(inputs)?);
+
+    let tag = match note_type {
+        NoteType::Public => public_tag(0, 0, NoteExecutionMode::Local)?,
+        _ => NoteTag::for_local_use_case(0, 0)?,
+    };
+    let metadata = NoteMetadata::new(
+        maker.id(),
+        note_type,
+        tag,
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )?;
+    let note = Note::new(NoteAssets::new(vec![offered])?, metadata, recipient);
+
+    let swap_req = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()?;
+    let tx_exec = client.new_transaction(maker.id(), swap_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    let payback = NoteDetails::new(NoteAssets::new(requested)?, payback_recipient);
+    Ok((note, payback))
+}
+
+/// Computes the ID a note will have without building the `Note`.
+///
+/// A note ID commits to the recipient and the assets only; the metadata (sender, tag, type) is
+/// not part of it, so notes that differ only in metadata share an ID.
+///
+/// # Arguments
+///
+/// * `assets` - The assets of the note.
+/// * `recipient` - The recipient of the note.
+///
+/// # Returns
+///
+/// Returns the `NoteId`, equal to `Note::new(assets, metadata, recipient).id()`.
+pub fn note_id(assets: &NoteAssets, recipient: &NoteRecipient) -> NoteId {
+    NoteId::new(recipient.digest(), assets.commitment())
+}
+
+/// Renders the details of a note as multi-line, human-readable text.
+///
+/// The output lists the note ID, sender, type, tag, assets, inputs and script root, one per
+/// line, with one indented line per asset.
+///
+/// # Arguments
+///
+/// * `note` - The note to describe.
+///
+/// # Returns
+///
+/// Returns the description, without a trailing newline.
+pub fn describe_note(note: &Note) -> String {
+    let metadata = note.metadata();
+    let mut lines = vec![
+        format!("note {}", note.id().to_hex()),
+        format!("  sender:      {}", metadata.sender()),
+        format!("  type:        {:?}", metadata.note_type()),
+        format!("  tag:         {}", metadata.tag()),
+        format!("  assets:      {}", note.assets().num_assets()),
+    ];
+    for asset in note.assets().iter() {
+        lines.push(match asset {
+            Asset::Fungible(asset) => {
+                format!("    {} of faucet {}", asset.amount(), asset.faucet_id())
+            }
+            Asset::NonFungible(asset) => format!(
+                "    non-fungible {} of faucet {}",
+                Word::from(*asset).to_hex(),
+                asset.faucet_id_prefix().to_hex()
+            ),
+        });
+    }
+    let inputs: Vec<String> = note
+        .inputs()
+        .values()
+        .iter()
+        .map(|input| input.as_int().to_string())
+        .collect();
+    lines.push(format!("  inputs:      [{}]", inputs.join(", ")));
+    lines.push(format!("  script root: {}", note.script().root().to_hex()));
+    lines.join("\n")
+}
+
+/// Computes the nullifiers of a batch of notes.
+///
+/// A nullifier commits to the serial number, script, inputs and assets of a note, so it does not
+/// depend on which account consumes the note: whoever consumes it publishes the same nullifier.
+/// This is why, unlike a per-account check, no account is needed here.
+///
+/// # Arguments
+///
+/// * `notes` - The notes to compute the nullifiers of.
+///
+/// # Returns
+///
+/// Returns the nullifiers, in the same order as `notes`.
+pub fn nullifiers_for(notes: &[Note]) -> Vec<Nullifier> {
+    notes.iter().map(Note::nullifier).collect()
+}
+
+/// Number of times the helpers in this crate retry a submission that failed transiently.
+pub const SUBMIT_RETRIES: u32 = 3;
+
+/// Base delay between submission retries; it doubles after every attempt.
+pub const SUBMIT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Returns `true` if the error is a transient RPC failure that is worth retrying.
+///
+/// Only failures to connect to the node count as transient. Errors returned by the node itself
+/// (including timeouts after the request was sent) are not retried. A connection that drops
+/// after the request was sent is still reported as a connection error, so the node may already
+/// have processed a request that is retried.
+pub fn is_transient_error(err: &ClientError) -> bool {
+    matches!(err, ClientError::RpcError(RpcError::ConnectionError(_)))
+}
+
+/// Returns the gRPC status code the node rejected a request with.
+///
+/// The client only keeps the formatted `tonic::Status` of a failed request, so the code is read
+/// back from the `status: ...` part of the message, which holds either the code's name or its
+/// description depending on the `tonic` version.
+fn grpc_status_code(err: &RpcError) -> Option<tonic::Code> {
+    let RpcError::RequestError(_, message) = err else {
+        return None;
+    };
+    let status = message.split_once("status: ")?.1.trim_start_matches('\'');
+    (0..=16).map(tonic::Code::from_i32).find(|code| {
+        status.starts_with(code.description()) || status.starts_with(&format!("{code:?}"))
+    })
+}
+
+/// Runs an operation, retrying it with exponential backoff while it fails transiently.
+///
+/// Every retry is logged as a `tracing` event at `warn` level.
+///
+/// # Arguments
+///
+/// * `max_retries` - The maximum number of retries after the first attempt.
+/// * `base_delay` - The delay before the first retry; it doubles after every attempt.
+/// * `op` - The operation to run.
+///
+/// # Returns
+///
+/// Returns the first successful result, the first non-transient error, or the last transient
+/// error once the retries are exhausted.
+pub async fn retry_on_transient<T, F>(
+    max_retries: u32,
+    base_delay: Duration,
+    mut op: F,
+) -> Result<T, ClientError>
+where
+    F: AsyncFnMut() -> Result<T, ClientError>,
+{
+    let mut delay = base_delay;
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Err(err) if attempt < max_retries && is_transient_error(&err) => {
+                attempt += 1;
+                tracing::warn!(attempt, max_retries, error = %err, "transient error, retrying");
+                sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Submits a transaction, retrying on transient RPC failures.
+///
+/// The transaction is submitted through `retry_on_transient`, so see `is_transient_error` for
+/// which failures are retried. A connection error can also be reported after the node has
+/// already accepted the transaction, so before every retry the client syncs and stops if the
+/// transaction is already recorded as pending or committed. If the node still reports the
+/// resubmitted transaction as already known (`AlreadyExists`), it counts as submitted.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `tx_result` - The executed transaction to prove and submit.
+/// * `max_retries` - The maximum number of retries after the first attempt.
+/// * `base_delay` - The delay before the first retry; it doubles after every attempt.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the transaction has been submitted.
+pub async fn submit_transaction_with_retry<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    tx_result: TransactionResult,
+    max_retries: u32,
+    base_delay: Duration,
+) -> Result<(), ClientError> {
+    let tx_id = tx_result.executed_transaction().id();
+    let mut resubmission = false;
+    retry_on_transient(max_retries, base_delay, async || {
+        if resubmission && transaction_recorded(client, tx_id).await? {
+            tracing::info!(%tx_id, "transaction already accepted, not resubmitting");
+            return Ok(());
+        }
+        let result = client.submit_transaction(tx_result.clone()).await;
+        let already_known = match &result {
+            Err(ClientError::RpcError(err)) => {
+                resubmission && grpc_status_code(err) == Some(tonic::Code::AlreadyExists)
+            }
+            _ => false,
+        };
+        resubmission = true;
+        if already_known {
+            tracing::info!(%tx_id, "node already knows the resubmitted transaction");
+            return Ok(());
+        }
+        result
+    })
+    .await
+}
+
+/// Syncs the client and returns whether the transaction is recorded as pending or committed.
+async fn transaction_recorded<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    tx_id: TransactionId,
+) -> Result<bool, ClientError> {
+    client.sync_state().await?;
+    let recorded = client
+        .get_transactions(TransactionFilter::Ids(vec![tx_id]))
+        .await?
+        .into_iter()
+        .any(|record| {
+            matches!(
+                record.status,
+                TransactionStatus::Pending | TransactionStatus::Committed { .. }
+            )
+        });
+    Ok(recorded)
+}
+
+/// Proves and submits a transaction, measuring how long it took.
+///
+/// The duration covers both proving, with whichever prover the client is configured with, and
+/// the submission round trip, so it can be used to compare local and remote provers. It is also
+/// emitted as a `tracing` event at `info` level.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `tx_result` - The executed transaction to prove and submit.
+///
+/// # Returns
+///
+/// Returns the ID of the submitted transaction and the time spent proving and submitting it.
+pub async fn submit_transaction_timed<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    tx_result: TransactionResult,
+) -> Result<(TransactionId, Duration), ClientError> {
+    let tx_id = tx_result.executed_transaction().id();
+
+    let start = Instant::now();
+    client.submit_transaction(tx_result).await?;
+    let elapsed = start.elapsed();
+
+    tracing::info!(
+        tx_id = %tx_id,
+        elapsed_ms = elapsed.as_millis() as u64,
+        "transaction proven and submitted"
+    );
+
+    Ok((tx_id, elapsed))
+}
+
+/// Lists the output notes created by an account, as tracked by the client.
+///
+/// Each returned record carries its state, so `OutputNoteRecord::is_committed` tells whether the
+/// note has been committed on-chain.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account whose notes to list, e.g. a faucet to trace its emissions.
+///
+/// # Returns
+///
+/// Returns the output note records whose sender is `account_id`.
+pub async fn notes_created_by<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+) -> Result<Vec<OutputNoteRecord>, ClientError> {
+    let notes = client.get_output_notes(NoteFilter::All).await?;
+    Ok(notes
+        .into_iter()
+        .filter(|output_note| output_note.metadata().sender() == account_id)
+        .collect())
+}
+
+/// A mint performed by a faucet, as returned by `faucet_mint_history`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintRecord {
+    /// The note carrying the minted asset.
+    pub note_id: NoteId,
+    /// The amount minted.
+    pub amount: u64,
+    /// The target account, if the note is a P2ID note whose recipient is known to the client.
+    pub target: Option<AccountId>,
+    /// The block the note was included in, if it has been committed.
+    pub block_num: Option<BlockNumber>,
+}
+
+/// Lists the mints performed by a faucet, as tracked by the client.
+///
+/// Every output note created by the faucet that carries its asset counts as a mint, see
+/// `notes_created_by`. Only mints submitted through this client are known.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `faucet_id` - The faucet whose mints to list.
+///
+/// # Returns
+///
+/// Returns one `MintRecord` per minted note.
+pub async fn faucet_mint_history<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    faucet_id: AccountId,
+) -> Result<Vec<MintRecord>, ClientError> {
+    let p2id_root = WellKnownNote::P2ID.script_root();
+
+    Ok(notes_created_by(client, faucet_id)
+        .await?
+        .into_iter()
+        .filter_map(|record| {
+            let amount: u64 = record
+                .assets()
+                .iter()
+                .filter_map(|asset| match asset {
+                    Asset::Fungible(asset) if asset.faucet_id() == faucet_id => {
+                        Some(asset.amount())
+                    }
+                    _ => None,
+                })
+                .sum();
+            if amount == 0 {
+                return None;
+            }
+
+            // P2ID inputs are the target account ID as `[suffix, prefix]`
+            let target = record
+                .recipient()
+                .filter(|recipient| recipient.script().root() == p2id_root)
+                .and_then(|recipient| match recipient.inputs().values() {
+                    [suffix, prefix] => AccountId::try_from([*prefix, *suffix]).ok(),
+                    _ => None,
+                });
+
+            Some(MintRecord {
+                note_id: record.id(),
+                amount,
+                target,
+                block_num: record
+                    .inclusion_proof()
+                    .map(|proof| proof.location().block_num()),
+            })
+        })
+        .collect())
+}
+
+/// Consumes a note and splits its fungible asset into several P2ID notes.
+///
+/// If the splits add up to less than the note's amount, the remainder is returned to `account`
+/// in an additional P2ID change note.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account consuming the input note and sending the outputs.
+/// * `input_note` - The note to split; it must carry exactly one fungible asset.
+/// * `splits` - Pairs of target account and amount.
+/// * `note_type` - The type of the output notes.
 ///
 /// # Returns
 ///
-/// Returns the created faucet `Account`.
-pub async fn create_basic_faucet(
-    client: &mut Client,
-    keystore: FilesystemKeyStore<StdRng>,
-) -> Result<miden_client::account::Account, ClientError> {
-    let mut init_seed = [0u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-    let key_pair = SecretKey::with_rng(client.rng());
-    let symbol = TokenSymbol::new("MID").unwrap();
-    let decimals = 8;
-    let max_supply = Felt::new(1_000_000);
-    let builder = AccountBuilder::new(init_seed)
-        .account_type(AccountType::FungibleFaucet)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key()))
-        .with_component(BasicFungibleFaucet::new(symbol, decimals, max_supply).unwrap());
-    let (account, seed) = builder.build().unwrap();
-    client.add_account(&account, Some(seed), false).await?;
-    keystore
-        .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
-        .unwrap();
-    Ok(account)
+/// Returns the created notes, with the change note (if any) last.
+pub async fn split_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    input_note: Note,
+    splits: &[(AccountId, u64)],
+    note_type: NoteType,
+) -> Result<Vec<Note>, ToolsError> {
+    let asset = match input_note.assets().iter().collect::<Vec<_>>().as_slice() {
+        [Asset::Fungible(asset)] => *asset,
+        _ => return Err(ToolsError::NotSingleFungibleAsset(input_note.id())),
+    };
+
+    let requested = splits
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .unwrap_or(u64::MAX);
+    if requested > asset.amount() {
+        return Err(ToolsError::InsufficientAmount {
+            available: asset.amount(),
+            requested,
+        });
+    }
+
+    let change = asset.amount() - requested;
+    let outputs = splits
+        .iter()
+        .copied()
+        .chain((change > 0).then_some((account.id(), change)));
+
+    let mut notes = Vec::new();
+    for (target, amount) in outputs {
+        let split_asset =
+            FungibleAsset::new(asset.faucet_id(), amount).map_err(ClientError::AssetError)?;
+        notes.push(create_p2id_note(
+            client,
+            account.id(),
+            target,
+            vec![split_asset.into()],
+            note_type,
+        )?);
+    }
+
+    let split_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(input_note, None)])
+        .own_output_notes(notes.iter().cloned().map(OutputNote::Full))
+        .build()?;
+    let tx_exec = client.new_transaction(account.id(), split_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
+    client.sync_state().await?;
+
+    Ok(notes)
 }
 
-/// Sets up a specified number of accounts and faucets, and mints tokens for each account.
+/// Drains every asset held by an account into P2ID notes for another account.
 ///
-/// This function creates a set of basic accounts and faucets, and mints tokens from each faucet to the accounts
-/// based on the given balance matrix.
+/// All assets from the sender's vault, across every faucet, are carried by a single multi-asset
+/// P2ID note. If the vault holds more assets than one note can carry, they are spread over as
+/// many notes as needed. An empty vault creates no notes and submits no transaction.
 ///
 /// # Arguments
 ///
 /// * `client` - The Miden client used to interact with the blockchain.
-/// * `keystore` - The keystore used to securely store account keys.
-/// * `num_accounts` - The number of accounts to create.
-/// * `num_faucets` - The number of faucets to create.
-/// * `balances` - A matrix where each entry represents the number of tokens to mint from a faucet to an account.
+/// * `from` - The account whose assets are swept.
+/// * `to` - The account the P2ID notes are addressed to.
+/// * `note_type` - The type of the created notes.
 ///
 /// # Returns
 ///
-/// Returns a tuple containing the created accounts and faucets as vectors.
-pub async fn setup_accounts_and_faucets(
-    client: &mut Client,
-    keystore: FilesystemKeyStore<StdRng>,
-    num_accounts: usize,
-    num_faucets: usize,
-    balances: Vec<Vec<u64>>,
-) -> Result<(Vec<Account>, Vec<Account>), ClientError> {
-    let mut accounts = Vec::with_capacity(num_accounts);
-    for i in 0..num_accounts {
-        let (account, _) = create_basic_account(client, keystore.clone()).await?;
-        println!("Created Account #{i} => ID: {:?}", account.id());
-        accounts.push(account);
+/// Returns the created P2ID notes.
+pub async fn sweep_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    from: &Account,
+    to: AccountId,
+    note_type: NoteType,
+) -> Result<Vec<Note>, ClientError> {
+    client.sync_state().await?;
+    let account = client
+        .get_account(from.id())
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(from.id()))?
+        .account()
+        .clone();
+
+    let assets: Vec<Asset> = account.vault().assets().collect();
+    if assets.is_empty() {
+        return Ok(Vec::new());
     }
 
-    let mut faucets = Vec::with_capacity(num_faucets);
-    for j in 0..num_faucets {
-        let faucet = create_basic_faucet(client, keystore.clone()).await?;
-        println!("Created Faucet #{j} => ID: {:?}", faucet.id());
-        faucets.push(faucet);
+    let mut notes = Vec::new();
+    for chunk in assets.chunks(NoteAssets::MAX_NUM_ASSETS) {
+        notes.push(create_p2id_note(
+            client,
+            from.id(),
+            to,
+            chunk.to_vec(),
+            note_type,
+        )?);
     }
 
+    let sweep_req = TransactionRequestBuilder::new()
+        .own_output_notes(notes.iter().cloned().map(OutputNote::Full))
+        .build()?;
+    let tx_exec = client.new_transaction(from.id(), sweep_req).await?;
+    submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
     client.sync_state().await?;
 
-    for (acct_index, account) in accounts.iter().enumerate() {
-        for (faucet_index, faucet) in faucets.iter().enumerate() {
-            let amount_to_mint = balances[acct_index][faucet_index];
-            if amount_to_mint == 0 {
-                continue;
+    Ok(notes)
+}
+
+/// Syncs until the client has caught up with the chain tip for a given account.
+///
+/// On every poll the client syncs, then compares its sync height with the latest block reported
+/// by the node. For public accounts known to the node, the local account commitment must also
+/// match the on-chain one, so state changes such as a consumed mint are reflected locally.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account_id` - The account that must be up to date.
+/// * `timeout` - The maximum time to wait.
+///
+/// # Returns
+///
+/// Returns the block height the client synced to, `ToolsError::Timeout`, or the error of a failed
+/// node request.
+#[cfg(feature = "testing")]
+pub async fn sync_account_to_tip<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    timeout: Duration,
+) -> Result<u32, ToolsError> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let tip_header = latest_block_header(client).await?;
+        let sync_height = client.get_sync_height().await?.as_u32();
+
+        let local_commitment = client
+            .get_account(account_id)
+            .await?
+            .map(|record| record.account().commitment())
+            .ok_or(ClientError::AccountDataNotFound(account_id))?;
+        let account_synced = if account_id.is_public() {
+            match client.test_rpc_api().get_account_details(account_id).await {
+                Ok(fetched) => fetched.commitment() == local_commitment,
+                Err(err) if is_not_found(&err) => true,
+                Err(err) => return Err(ClientError::from(err).into()),
             }
+        } else {
+            true
+        };
 
-            println!(
-                "Minting {amount_to_mint} tokens from Faucet #{faucet_index} to Account #{acct_index}"
-            );
+        if sync_height >= tip_header.block_num().as_u32() && account_synced {
+            return Ok(sync_height);
+        }
 
-            let fungible_asset = FungibleAsset::new(faucet.id(), amount_to_mint).unwrap();
-            let tx_req = TransactionRequestBuilder::new()
-                .build_mint_fungible_asset(
-                    fungible_asset,
-                    account.id(),
-                    NoteType::Public,
-                    client.rng(),
-                )
-                .unwrap();
+        if Instant::now() >= deadline {
+            return Err(ToolsError::Timeout);
+        }
 
-            let tx_exec = client.new_transaction(faucet.id(), tx_req).await?;
-            client.submit_transaction(tx_exec.clone()).await?;
+        tracing::debug!(%account_id, sync_height, "account not synced to tip yet, waiting");
+        sleep(Duration::from_secs(1)).await;
+    }
+}
 
-            let minted_note = if let OutputNote::Full(note) = tx_exec.created_notes().get_note(0) {
-                note.clone()
-            } else {
-                panic!("Expected OutputNote::Full, but got something else");
-            };
+/// Compiles a transaction script and signs a message for it to verify.
+///
+/// The message is signed with `key`, and the signature is laid out with
+/// `generate_advice_stack_from_signature` as the advice stack of the returned `AdviceInputs`.
+/// The script can then verify it with `std::crypto::dsa::rpo_falcon512::verify`, given the public
+/// key and the message on the operand stack.
+///
+/// # Arguments
+///
+/// * `script_code` - The code for the transaction script, typically written in MASM.
+/// * `library` - An optional library to use with the script.
+/// * `key` - The key to sign the message with.
+/// * `message` - The message to sign.
+///
+/// # Returns
+///
+/// Returns the compiled `TransactionScript` and the advice inputs holding the signature, or
+/// `ToolsError::InvalidScript` if the script does not compile.
+pub fn signed_tx_script(
+    script_code: String,
+    library: Option<Library>,
+    key: &SecretKey,
+    message: Word,
+) -> Result<(TransactionScript, AdviceInputs), ToolsError> {
+    let tx_script = create_tx_script(script_code, library)
+        .map_err(|err| ToolsError::InvalidScript(err.to_string()))?;
 
-            wait_for_note(client, &minted_note).await?;
-            client.sync_state().await?;
+    // the client's signature polynomials come from its own `miden-crypto` version, so convert
+    // them coefficient by coefficient
+    let signature = key.sign(message);
+    let h = falcon_polynomial(
+        signature
+            .pk_poly()
+            .0
+            .coefficients
+            .iter()
+            .map(|c| c.value() as u16),
+    );
+    let s2 = falcon_polynomial(
+        signature
+            .sig_poly()
+            .coefficients
+            .iter()
+            .map(|c| c.value() as u16),
+    );
+    let advice_stack = generate_advice_stack_from_signature(h, s2);
 
-            let consume_req = TransactionRequestBuilder::new()
-                .authenticated_input_notes([(minted_note.id(), None)])
-                .build()
-                .unwrap();
+    let advice_inputs = AdviceInputs::default().with_stack(advice_stack.into_iter().map(Felt::new));
 
-            let tx_exec = client.new_transaction(account.id(), consume_req).await?;
-            client.submit_transaction(tx_exec).await?;
-            client.sync_state().await?;
-        }
-    }
+    Ok((tx_script, advice_inputs))
+}
 
-    Ok((accounts, faucets))
+/// Builds a `Polynomial<Felt>` from Falcon coefficients in `[0, q)`.
+fn falcon_polynomial(coefficients: impl Iterator<Item = u16>) -> Polynomial<Felt> {
+    Polynomial::new(coefficients.map(Felt::from).collect())
 }
 
-/// Mints tokens from a faucet to an account.
+/// Generates a fresh Falcon-512 key pair together with its public key polynomial.
 ///
-/// This function mints a specified amount of tokens from a faucet to an account, and waits for the transaction
-/// to be confirmed. It optionally executes a custom transaction script if provided.
+/// The returned `h` is the polynomial expected by `generate_advice_stack_from_signature`, so
+/// signatures made with the secret key can be laid out as advice without creating an account.
+///
+/// # Returns
+///
+/// Returns the secret key and its public key polynomial `h`.
+pub fn falcon_keypair() -> (SecretKey, Polynomial<Felt>) {
+    let key = SecretKey::with_rng(&mut StdRng::from_os_rng());
+    let h = falcon_polynomial(
+        key.compute_pub_key_poly()
+            .0
+            .coefficients
+            .iter()
+            .map(|c| c.value() as u16),
+    );
+    (key, h)
+}
+
+/// Returns the header of the latest block known to the node, after syncing the client.
 ///
 /// # Arguments
 ///
 /// * `client` - The Miden client used to interact with the blockchain.
-/// * `account` - The account that will receive the tokens.
+///
+/// # Returns
+///
+/// Returns the chain tip's `BlockHeader`.
+#[cfg(feature = "testing")]
+pub async fn latest_block_header<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+) -> Result<BlockHeader, ClientError> {
+    client.sync_state().await?;
+    let (block_header, _) = client
+        .test_rpc_api()
+        .get_block_header_by_number(None, false)
+        .await?;
+    Ok(block_header)
+}
+
+/// Creates a basic account and funds it from a faucet.
+///
+/// The account is created, a note with `amount` tokens is minted to it, and once the note is
+/// committed the account consumes it.
+///
+/// # Arguments
+///
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `keystore` - The keystore to store the account's secret key.
 /// * `faucet` - The faucet to mint tokens from.
 /// * `amount` - The number of tokens to mint.
-/// * `tx_script` - An optional custom transaction script to execute after the minting transaction. If `None`, no script is executed.
+/// * `note_type` - The type of the minted note.
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating whether the minting process was successful or not. If the transaction script is provided, it will also be executed
-/// after the minting process, otherwise, only the minting transaction is processed.
-pub async fn mint_from_faucet_for_account(
-    client: &mut Client,
-    account: &Account,
+/// Returns the funded `Account`, with its vault already holding the tokens, and its `SecretKey`,
+/// or `ToolsError::PartialOutputNote` if the minted note is not fully known to the client.
+pub async fn create_funded_account<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: K,
     faucet: &Account,
     amount: u64,
-    tx_script: Option<TransactionScript>, // Make tx_script optional
-) -> Result<(), ClientError> {
-    if amount == 0 {
-        return Ok(());
-    }
-
-    let asset = FungibleAsset::new(faucet.id(), amount).unwrap();
-    let mint_req = TransactionRequestBuilder::new()
-        .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
-        .unwrap();
+    note_type: NoteType,
+) -> Result<(Account, SecretKey), ToolsError> {
+    let (account, key_pair) = create_basic_account(client, keystore).await?;
+    client.sync_state().await?;
 
+    let asset = FungibleAsset::new(faucet.id(), amount).map_err(ClientError::AssetError)?;
+    let mint_req = TransactionRequestBuilder::new().build_mint_fungible_asset(
+        asset,
+        account.id(),
+        note_type,
+        client.rng(),
+    )?;
     let mint_exec = client.new_transaction(faucet.id(), mint_req).await?;
-    client.submit_transaction(mint_exec.clone()).await?;
+    submit_transaction_with_retry(
+        client,
+        mint_exec.clone(),
+        SUBMIT_RETRIES,
+        SUBMIT_RETRY_DELAY,
+    )
+    .await?;
 
     let minted_note = match mint_exec.created_notes().get_note(0) {
         OutputNote::Full(note) => note.clone(),
-        _ => panic!("Expected full minted note"),
-    };
-
-    let consume_req = if let Some(script) = tx_script {
-        TransactionRequestBuilder::new()
-            .unauthenticated_input_notes([(minted_note, None)])
-            .custom_script(script)
-            .build()?
-    } else {
-        TransactionRequestBuilder::new()
-            .unauthenticated_input_notes([(minted_note, None)])
-            .build()?
+        output_note => return Err(ToolsError::PartialOutputNote(output_note.id())),
     };
+    wait_for_note(client, &minted_note).await?;
 
-    let consume_exec = client
-        .new_transaction(account.id(), consume_req)
-        .await
-        .unwrap();
-
-    client.submit_transaction(consume_exec.clone()).await?;
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(minted_note, None)])
+        .build()?;
+    let consume_exec = client.new_transaction(account.id(), consume_req).await?;
+    submit_transaction_with_retry(client, consume_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY).await?;
     client.sync_state().await?;
 
-    Ok(())
+    let account = client
+        .get_account(account.id())
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(account.id()))?
+        .account()
+        .clone();
+
+    Ok((account, key_pair))
 }
 
-/// Creates a public note in the blockchain.
+/// Lists the keystore keys that no account tracked by the client references.
 ///
-/// This function creates a public note using the provided note code, account library (if any), and other
-/// related parameters.
+/// A key counts as referenced if its public key is stored in one of the storage slots of a
+/// tracked account, which is where auth components such as `AuthRpoFalcon512` keep it. Orphaned
+/// keys typically remain after deleting a store but not its keystore.
 ///
 /// # Arguments
 ///
 /// * `client` - The Miden client used to interact with the blockchain.
-/// * `note_code` - The code for the note, typically written in MASM.
-/// * `account_library` - An optional library that might be used during note creation.
-/// * `creator_account` - The account creating the note.
-/// * `assets` - The assets associated with the note (optional).
-/// * `note_inputs` - The inputs associated with the note (optional).
+/// * `keystore` - The keystore to inspect.
 ///
 /// # Returns
 ///
-/// Returns a `Result` containing the created `Note` or an error.
-pub async fn create_public_note(
-    client: &mut Client,
-    note_code: String,
-    account_library: Option<Library>,
-    creator_account: Account,
-    assets: Option<NoteAssets>,
-    note_inputs: Option<NoteInputs>,
-) -> Result<Note, ClientError> {
-    let assembler = if let Some(library) = account_library {
-        TransactionKernel::assembler()
-            .with_dynamic_library(&library)
-            .unwrap()
-    } else {
-        TransactionKernel::assembler()
+/// Returns the public keys of the orphaned keys.
+pub async fn find_orphaned_keys<AUTH: TransactionAuthenticator + Sync + 'static, K: KeyStore>(
+    client: &mut Client<AUTH>,
+    keystore: &K,
+) -> Result<Vec<PublicKey>, ToolsError> {
+    let mut referenced = HashSet::new();
+    for (header, _) in client.get_account_headers().await? {
+        if let Some(record) = client.get_account(header.id()).await? {
+            referenced.extend(
+                record
+                    .account()
+                    .storage()
+                    .slots()
+                    .iter()
+                    .map(|slot| slot.value()),
+            );
+        }
     }
-    .with_debug_mode(true);
-
-    let rng = client.rng();
-    let serial_num = rng.draw_word();
-    let program = assembler.clone().assemble_program(note_code).unwrap();
-    let note_script = NoteScript::new(program);
-
-    let note_inputs = note_inputs.unwrap_or_else(|| NoteInputs::new([].to_vec()).unwrap());
-    let assets = assets.unwrap_or_else(|| NoteAssets::new(vec![]).unwrap());
-
-    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs.clone());
-    let tag = NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local).unwrap();
-    let metadata = NoteMetadata::new(
-        creator_account.id(),
-        NoteType::Public,
-        tag,
-        NoteExecutionHint::always(),
-        Felt::new(0),
-    )
-    .unwrap();
 
-    let note = Note::new(assets, metadata, recipient);
-
-    let note_req = TransactionRequestBuilder::new()
-        .own_output_notes(vec![OutputNote::Full(note.clone())])
-        .build()
-        .unwrap();
-    let tx_result = client
-        .new_transaction(creator_account.id(), note_req)
-        .await?;
-
-    client.submit_transaction(tx_result).await?;
-    client.sync_state().await?;
-
-    Ok(note)
+    let keys = keystore.secret_keys().map_err(ToolsError::KeyStore)?;
+    Ok(keys
+        .into_iter()
+        .filter_map(|key| match key {
+            AuthSecretKey::RpoFalcon512(secret_key) => Some(secret_key.public_key()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+        .filter(|public_key| !referenced.contains(&Word::from(*public_key)))
+        .collect())
 }
 
-/// Waits for the exact note to be available and committed.
+/// Checks whether a keystore holds the secret key for an account's auth public key.
 ///
-/// This function will block until the specified note is found in the output notes and is committed.
+/// The account's auth public key is looked up the same way as in `find_orphaned_keys`: as a
+/// storage slot value, which is where auth components such as `AuthRpoFalcon512` keep it. Call
+/// this before submitting a transaction to catch a missing key early, instead of getting a
+/// signing failure from the authenticator.
 ///
 /// # Arguments
 ///
-/// * `client` - The Miden client used to interact with the blockchain.
-/// * `expected` - The note to wait for.
+/// * `keystore` - The keystore to inspect.
+/// * `account` - The account whose key to look for.
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating whether the note was found and committed.
-pub async fn wait_for_note(client: &mut Client, expected: &Note) -> Result<(), ClientError> {
-    loop {
-        client.sync_state().await?;
-
-        let notes = client.get_output_notes(NoteFilter::All).await?;
-
-        // Check if the expected note is in the output notes and is committed
-        let found = notes
-            .iter()
-            .any(|output_note| output_note.id() == expected.id() && output_note.is_committed());
+/// Returns `true` if the keystore holds the key; `false` if it does not or cannot be read.
+pub fn keystore_has_key_for<K: KeyStore>(keystore: &K, account: &Account) -> bool {
+    let Ok(keys) = keystore.secret_keys() else {
+        return false;
+    };
 
-        if found {
-            println!("✅ note found and committed {}", expected.id().to_hex());
-            break;
+    let slot_values: HashSet<Word> = account
+        .storage()
+        .slots()
+        .iter()
+        .map(|slot| slot.value())
+        .collect();
+    keys.into_iter().any(|key| match key {
+        AuthSecretKey::RpoFalcon512(secret_key) => {
+            slot_values.contains(&Word::from(secret_key.public_key()))
         }
-
-        println!("Note {} not found. Waiting...", expected.id().to_hex());
-        sleep(Duration::from_secs(3)).await;
-    }
-    Ok(())
+        #[allow(unreachable_patterns)]
+        _ => false,
+    })
 }
 
-/// Creates a transaction script based on the provided code and optional library.
+/// Checks whether an account can consume a note by executing the consume transaction locally.
+///
+/// The transaction is executed but neither proven nor submitted, so the check costs one
+/// execution and leaves the chain untouched. Notes whose inputs the account cannot satisfy, e.g.
+/// a P2ID note targeting another account, fail in the VM and yield `false`; any other executor
+/// failure, such as missing transaction inputs, is returned as an error.
 ///
 /// # Arguments
 ///
-/// * `script_code` - The code for the transaction script, typically written in MASM.
-/// * `library` - An optional library to use with the script.
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account that would consume the note.
+/// * `note` - The note to check.
 ///
 /// # Returns
 ///
-/// Returns a `TransactionScript` if successfully created, or an error.
-pub fn create_tx_script(
-    script_code: String,
-    library: Option<Library>,
-) -> Result<TransactionScript, Error> {
-    let tx_script = match library {
-        Some(lib) => ScriptBuilder::new(true)
-            .with_dynamically_linked_library(&lib)
-            .unwrap()
-            .compile_tx_script(script_code)
-            .unwrap(),
-        None => ScriptBuilder::new(true)
-            .compile_tx_script(script_code)
-            .unwrap(),
-    };
-    Ok(tx_script)
+/// Returns whether the consume transaction executes, or a `ClientError` if the check itself
+/// could not be run.
+pub async fn can_consume<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    note: &Note,
+) -> Result<bool, ClientError> {
+    Ok(execute_consume(client, account.id(), note.clone())
+        .await?
+        .is_some())
 }
 
-/// Creates a public-to-ID (p2id) note for a specified sender and target account.
+/// Executes the transaction consuming `note`, or returns `None` if the note fails in the VM.
+async fn execute_consume<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    note: Note,
+) -> Result<Option<TransactionResult>, ClientError> {
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(note, None)])
+        .build()?;
+
+    match client.new_transaction(account_id, consume_req).await {
+        Ok(tx_result) => Ok(Some(tx_result)),
+        Err(ClientError::TransactionExecutorError(
+            TransactionExecutorError::TransactionProgramExecutionFailed(_),
+        )) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Consumes notes one at a time until a deadline, reporting per-note results.
+///
+/// Each note is consumed in its own transaction, so an unconsumable note only fails itself: if
+/// the consume transaction fails in the VM, as checked by `can_consume`, the note is reported
+/// with `ToolsError::NoteNotConsumable` and nothing is submitted for it. Every note is executed
+/// once, and the executed transaction is the one submitted. Notes not attempted before `deadline` are reported with
+/// `ToolsError::Timeout`.
 ///
 /// # Arguments
 ///
-/// * `sender` - The account ID of the sender.
-/// * `target` - The account ID of the target.
-/// * `assets` - The assets associated with the note.
-/// * `note_type` - The type of the note (e.g., public).
-/// * `aux` - Auxiliary data for the note.
-/// * `serial_num` - The serial number of the note.
+/// * `client` - The Miden client used to interact with the blockchain.
+/// * `account` - The account that will consume the notes.
+/// * `notes` - The notes to consume.
+/// * `deadline` - The instant after which no further note is attempted.
 ///
 /// # Returns
 ///
-/// Returns the created `Note`.
-pub fn create_exact_p2id_note(
-    sender: AccountId,
-    target: AccountId,
-    assets: Vec<Asset>,
-    note_type: NoteType,
-    aux: Felt,
-    serial_num: Word,
-) -> Result<Note, NoteError> {
-    let recipient = utils::build_p2id_recipient(target, serial_num)?;
-    let tag = NoteTag::from_account_id(target);
+/// Returns the IDs of the consumed notes and the errors of the notes that were not consumed.
+pub async fn consume_notes_deadline<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account: &Account,
+    notes: Vec<Note>,
+    deadline: Instant,
+) -> (Vec<NoteId>, Vec<(NoteId, ToolsError)>) {
+    let mut consumed = Vec::new();
+    let mut failed = Vec::new();
 
-    let metadata = NoteMetadata::new(sender, note_type, tag, NoteExecutionHint::always(), aux)?;
-    let vault = NoteAssets::new(assets)?;
+    for note in notes {
+        let note_id = note.id();
+        if Instant::now() >= deadline {
+            failed.push((note_id, ToolsError::Timeout));
+            continue;
+        }
 
-    Ok(Note::new(vault, metadata, recipient))
+        let result = async {
+            let Some(tx_exec) = execute_consume(client, account.id(), note).await? else {
+                return Err(ToolsError::NoteNotConsumable(note_id));
+            };
+            submit_transaction_with_retry(client, tx_exec, SUBMIT_RETRIES, SUBMIT_RETRY_DELAY)
+                .await?;
+            Ok::<(), ToolsError>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => consumed.push(note_id),
+            Err(err) => failed.push((note_id, err)),
+        }
+    }
+
+    if let Err(err) = client.sync_state().await {
+        tracing::warn!(error = %err, "failed to sync after consuming notes");
+    }
+
+    (consumed, failed)
 }
@@ -1,25 +1,72 @@
 use miden_client_tools::{
-    create_basic_account, create_exact_p2id_note, create_public_note, delete_keystore_and_store,
-    instantiate_client, mint_from_faucet_for_account, setup_accounts_and_faucets, wait_for_note,
+    AccountLibraries, AccountOnChainStatus, ClientConfig, CodeMutability, FaucetMetadata,
+    FullTxSpec, InMemoryKeyStore, KeyStore, MAX_SETUP_FAUCETS, Network, NoteAuthStatus,
+    NoteOptions, NoteWatcher, OutputNoteSpec, RoutingInfo, RpcLimiter, SyncController, SyncStatus,
+    ToolsError, account_exists_on_chain, account_tag, advice_stack_len, assert_no_dangling_notes,
+    assert_note_script, assert_p2id_target, await_created_note, balance_report, balances_to_csv,
+    build_full_request, build_multi_output_request, can_consume, check_store_integrity,
+    check_transfer_feasible, classify_endpoint, compact_store, compile_account_component,
+    component_storage_root, consume_and_assert_delta, consume_for_accounts,
+    consume_note_with_advice, consume_notes_by_tag, consume_notes_deadline, consume_notes_fifo,
+    consume_with_proof, create_account_and_advance_nonce, create_account_with_auth_keys,
+    create_accounts_mixed, create_basic_account, create_basic_account_anchored_latest,
+    create_custom_account, create_custom_account_checked, create_exact_p2id_note,
+    create_faucet_from_config, create_faucet_with_metadata, create_faucets, create_funded_account,
+    create_library_diagnostic, create_multi_asset_swap, create_note_from_script,
+    create_note_from_script_with_serials, create_p2id_note, create_private_note_with_details,
+    create_public_note, create_public_note_with_options, create_rotatable_account,
+    created_notes_of_type, decode_aux, delete_env, delete_keystore_and_store, describe_note,
+    deserialize_tx_request, deterministic_serial, diff_snapshots, encode_aux,
+    estimate_script_cycles, estimate_tx_script_cycles, execute_and_submit,
+    execute_and_submit_with_authenticator, export_transaction_proof, falcon_keypair,
+    faucet_mint_history, find_orphaned_keys, format_tx_error, generate_advice_stack_from_signature,
+    generate_advice_stack_with_challenge, import_note, import_note_bytes, import_notes_from_dir,
+    increment_counter, instantiate_client, instantiate_client_for, instantiate_client_in_memory,
+    instantiate_client_with_authenticator, instantiate_client_with_config, keystore_has_key_for,
+    latest_block_header, list_pending_transactions, merge_stores, mint_from_faucet_for_account,
+    mint_series, mint_to_recipient, note_auth_status, note_id, notes_created_by, nullifiers_for,
+    plan_setup, public_tag, record_to_note, replay_request, retry_on_transient, rotate_auth_key,
+    safe_note_assets, serialize_tx_request, setup_accounts_and_faucets, signed_tx_script,
+    simulate_at_sync_height, snapshot_account, split_note, storage_slots_from,
+    submit_noop_transaction, submit_pipeline, submit_transaction_timed, sweep_account,
+    sync_account_to_tip, tools_assembler, total_fungible_balance, verify_bundled_masm,
+    verify_transaction_proof, wait_for_balances, wait_for_note, wait_for_note_confirmed,
+    watch_account, watch_notes,
 };
 
 #[cfg(test)]
 mod tests {
     use std::fs;
     use std::path::Path;
+    use std::pin::pin;
 
     use super::*;
+    use futures::StreamExt;
     use miden_assembly::diagnostics::NamedSource;
     use miden_client::account::{AccountBuilder, AccountStorageMode, AccountType, StorageSlot};
     use miden_client::rpc::Endpoint;
     use miden_client::{
-        Felt, Word, account::AccountId, keystore::FilesystemKeyStore, note::NoteType,
+        ClientError, Felt, Word,
+        account::AccountId,
+        asset::{Asset, FungibleAsset, TokenSymbol},
+        auth::AuthSecretKey,
+        crypto::SecretKey,
+        keystore::FilesystemKeyStore,
+        note::{Note, NoteAssets, NoteExecutionMode, NoteFile, NoteInputs, NoteTag, NoteType},
+        rpc::RpcError,
+        transaction::{OutputNote, TransactionRequestBuilder},
     };
-    use miden_client_tools::{create_basic_faucet, create_library, create_tx_script};
+    use miden_client_tools::{LibraryCache, create_basic_faucet, create_library, create_tx_script};
+    use miden_crypto::dsa::rpo_falcon512::Polynomial;
     use miden_lib::account::auth::{self};
+    use miden_lib::account::faucets::BasicFungibleFaucet;
     use miden_lib::account::wallets::BasicWallet;
+    use miden_lib::note::{WellKnownNote, utils};
     use miden_lib::transaction::TransactionKernel;
+    use miden_objects::Hasher;
     use miden_objects::account::AccountComponent;
+    use miden_objects::testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET;
+    use miden_objects::utils::Serializable;
     use rand::RngCore;
 
     #[tokio::test]
@@ -86,6 +133,21 @@ mod tests {
         delete_keystore_and_store(None).await;
     }
 
+    #[tokio::test]
+    async fn test_create_basic_account_anchored_latest() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _, anchor) = create_basic_account_anchored_latest(&mut client, keystore)
+            .await
+            .unwrap();
+        assert_eq!(anchor, client.get_sync_height().await.unwrap());
+        assert!(client.get_account(account.id()).await.unwrap().is_some());
+
+        delete_keystore_and_store(None).await;
+    }
+
     #[tokio::test]
     async fn test_create_basic_faucet() {
         let endpoint = Endpoint::localhost();
@@ -112,6 +174,38 @@ mod tests {
         assert_eq!(accounts.len(), 2);
         assert_eq!(faucets.len(), 2);
 
+        // symbols run out after MIDZZZ, which is rejected before anything is created
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let result =
+            setup_accounts_and_faucets(&mut client, keystore, 0, MAX_SETUP_FAUCETS + 1, vec![])
+                .await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::TooManyFaucets { requested, max })
+                if requested == MAX_SETUP_FAUCETS + 1 && max == MAX_SETUP_FAUCETS
+        ));
+        assert_eq!(client.get_account_headers().await.unwrap().len(), 4);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_balance_report() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let balances = vec![vec![10, 0], vec![0, 25]];
+        let (accounts, faucets) =
+            setup_accounts_and_faucets(&mut client, keystore, 2, 2, balances.clone())
+                .await
+                .unwrap();
+
+        let report = balance_report(&mut client, &accounts, &faucets)
+            .await
+            .unwrap();
+        assert_eq!(report, balances);
+
         delete_keystore_and_store(None).await;
     }
 
@@ -221,6 +315,11 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_verify_bundled_masm() {
+        assert!(verify_bundled_masm().is_ok());
+    }
+
     #[tokio::test]
     async fn test_create_tx_script() {
         let script_code =
@@ -245,4 +344,3276 @@ mod tests {
         let note = create_exact_p2id_note(sender, target, assets, note_type, aux, serial_num);
         assert!(note.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_consume_notes_by_tag() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let minted_note = match mint_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note.clone(),
+            _ => panic!("Expected full minted note"),
+        };
+        wait_for_note(&mut client, &minted_note).await.unwrap();
+
+        let other_tag = NoteTag::for_public_use_case(1, 0, NoteExecutionMode::Local).unwrap();
+        let result = consume_notes_by_tag(&mut client, &account, other_tag).await;
+        assert!(matches!(result, Err(ToolsError::NoMatchingNotes)));
+
+        let consumed = consume_notes_by_tag(&mut client, &account, minted_note.metadata().tag())
+            .await
+            .unwrap();
+        assert_eq!(consumed, 1);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_p2id_note() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let note_1 =
+            create_p2id_note(&mut client, sender, target, vec![], NoteType::Public).unwrap();
+        let note_2 =
+            create_p2id_note(&mut client, sender, target, vec![], NoteType::Public).unwrap();
+
+        assert_eq!(note_1.metadata().sender(), sender);
+        assert_ne!(note_1.serial_num(), note_2.serial_num());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_library_cache_reuses_compiled_scripts() {
+        let script_code =
+            fs::read_to_string(Path::new("./masm/scripts/increment_script.masm")).unwrap();
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code, library_path).unwrap();
+
+        let cache = LibraryCache::new();
+        let first = cache.tx_script(&script_code, Some(&library)).unwrap();
+        let second = cache.tx_script(&script_code, Some(&library)).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(first, second);
+
+        // a different script compiles into a second entry
+        let unlinked = cache.tx_script("begin push.1 drop end", None).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_ne!(first.root(), unlinked.root());
+
+        // scripts that fail to compile are reported and not cached
+        let result = cache.tx_script("begin not_an_instruction end", None);
+        assert!(matches!(result, Err(ToolsError::InvalidScript(_))));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_public_notes_with_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let note_code = fs::read_to_string(Path::new("./masm/notes/increment_note.masm")).unwrap();
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code, library_path).unwrap();
+
+        let cache = LibraryCache::new();
+        let mut script_roots = Vec::new();
+        for _ in 0..2 {
+            let note = create_public_note_with_options(
+                &mut client,
+                note_code.clone(),
+                Some(library.clone()),
+                account.clone(),
+                None,
+                None,
+                NoteOptions::default().with_cache(&cache),
+            )
+            .await?;
+            script_roots.push(note.script().root());
+        }
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(script_roots[0], script_roots[1]);
+
+        delete_keystore_and_store(None).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_storage_slots_from() {
+        let value = Word::from([Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(5)]);
+
+        let slots = storage_slots_from(&[(2, value)]).unwrap();
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0], StorageSlot::empty_value());
+        assert_eq!(slots[2], StorageSlot::Value(value));
+
+        let result = storage_slots_from(&[(1, value), (1, Word::default())]);
+        assert!(matches!(result, Err(ToolsError::DuplicateStorageSlot(1))));
+    }
+
+    #[tokio::test]
+    async fn test_account_with_preset_storage() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let initial_count = Word::from([Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(5)]);
+        let storage_slots = storage_slots_from(&[(0, initial_count)]).unwrap();
+        let account_component = compile_account_component(account_code, storage_slots).unwrap();
+
+        let mut init_seed = [0_u8; 32];
+        client.rng().fill_bytes(&mut init_seed);
+
+        let (account, seed) = AccountBuilder::new(init_seed)
+            .account_type(AccountType::RegularAccountImmutableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_component(account_component)
+            .with_auth_component(auth::NoAuth)
+            .build()
+            .unwrap();
+        client
+            .add_account(&account, Some(seed), false)
+            .await
+            .unwrap();
+
+        let record = client.get_account(account.id()).await.unwrap().unwrap();
+        assert_eq!(
+            record.account().storage().get_item(0).unwrap(),
+            initial_count
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_note_confirmed() -> Result<(), Box<dyn std::error::Error>> {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let note_code = fs::read_to_string(Path::new("./masm/notes/increment_note.masm")).unwrap();
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code, library_path).unwrap();
+
+        let note = create_public_note(
+            &mut client,
+            note_code,
+            Some(library),
+            account.clone(),
+            None,
+            None,
+        )
+        .await?;
+
+        let result =
+            wait_for_note_confirmed(&mut client, &note, 2, std::time::Duration::from_secs(120))
+                .await;
+        assert!(result.is_ok());
+
+        delete_keystore_and_store(None).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_increment_counter() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code.clone(), library_path).unwrap();
+
+        let account_component =
+            compile_account_component(account_code, vec![StorageSlot::empty_value()]).unwrap();
+
+        let mut init_seed = [0_u8; 32];
+        client.rng().fill_bytes(&mut init_seed);
+
+        let (account, seed) = AccountBuilder::new(init_seed)
+            .account_type(AccountType::RegularAccountImmutableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_component(account_component)
+            .with_auth_component(auth::NoAuth)
+            .build()
+            .unwrap();
+        client
+            .add_account(&account, Some(seed), false)
+            .await
+            .unwrap();
+
+        let first = increment_counter(&mut client, &account, library.clone())
+            .await
+            .unwrap();
+        let second = increment_counter(&mut client, &account, library)
+            .await
+            .unwrap();
+        assert_eq!(second, first + 1);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_diff_account() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code.clone(), library_path).unwrap();
+
+        let account_component =
+            compile_account_component(account_code, vec![StorageSlot::empty_value()]).unwrap();
+
+        let mut init_seed = [0_u8; 32];
+        client.rng().fill_bytes(&mut init_seed);
+
+        let (account, seed) = AccountBuilder::new(init_seed)
+            .account_type(AccountType::RegularAccountImmutableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_component(account_component)
+            .with_auth_component(auth::NoAuth)
+            .build()
+            .unwrap();
+        client
+            .add_account(&account, Some(seed), false)
+            .await
+            .unwrap();
+
+        let before = snapshot_account(&mut client, account.id()).await.unwrap();
+        let count = increment_counter(&mut client, &account, library)
+            .await
+            .unwrap();
+        let after = snapshot_account(&mut client, account.id()).await.unwrap();
+
+        let diff = diff_snapshots(&before, &after);
+        assert_eq!(diff.nonce_delta, 1);
+        assert_eq!(
+            diff.storage_changes,
+            vec![(
+                0,
+                Word::default(),
+                Word::from([Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(count)])
+            )]
+        );
+        assert!(diff.balance_changes.is_empty());
+        assert!(diff_snapshots(&after, &after).is_empty());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_note_auth_status() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let minted_note = match mint_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note.clone(),
+            _ => panic!("Expected full minted note"),
+        };
+
+        let status = note_auth_status(&mut client, minted_note.id())
+            .await
+            .unwrap();
+        assert_eq!(status, NoteAuthStatus::Pending);
+
+        wait_for_note(&mut client, &minted_note).await.unwrap();
+        let status = note_auth_status(&mut client, minted_note.id())
+            .await
+            .unwrap();
+        assert_eq!(status, NoteAuthStatus::Authenticated);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_execute_and_submit_with_error_chain() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let script_code = "begin push.1 push.2 assert_eq end".to_string();
+        let tx_script = create_tx_script(script_code, None).unwrap();
+        let tx_req = TransactionRequestBuilder::new()
+            .custom_script(tx_script)
+            .build()
+            .unwrap();
+
+        let result = execute_and_submit(&mut client, account.id(), tx_req, true).await;
+        match result {
+            Err(ToolsError::ExecutionFailed { error_chain, .. }) => {
+                // the VM error at the end of the chain names the failing cycle
+                assert!(
+                    error_chain
+                        .iter()
+                        .any(|err| err.contains("assertion failed at clock cycle")),
+                    "{error_chain:?}"
+                );
+            }
+            other => panic!("expected execution failure, got {other:?}"),
+        }
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_consume_for_accounts() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account_1, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (account_2, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let mut minted_notes = Vec::new();
+        for account in [&account_1, &account_2] {
+            let asset = FungibleAsset::new(faucet.id(), 50).unwrap();
+            let mint_req = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+                .unwrap();
+            let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+            client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+            match mint_exec.created_notes().get_note(0) {
+                OutputNote::Full(note) => minted_notes.push(note.clone()),
+                _ => panic!("Expected full minted note"),
+            }
+        }
+
+        let assignments = vec![
+            (&account_1, vec![minted_notes[0].clone()]),
+            (&account_2, vec![minted_notes[1].clone()]),
+        ];
+        let result = consume_for_accounts(&mut client, &assignments).await;
+        assert!(result.is_ok());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_total_fungible_balance() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let balances = vec![vec![10, 20], vec![30, 40]];
+        let (_, faucets) = setup_accounts_and_faucets(&mut client, keystore, 2, 2, balances)
+            .await
+            .unwrap();
+
+        let total_0 = total_fungible_balance(&mut client, faucets[0].id())
+            .await
+            .unwrap();
+        let total_1 = total_fungible_balance(&mut client, faucets[1].id())
+            .await
+            .unwrap();
+        assert_eq!(total_0.total, 40);
+        assert_eq!(total_0.skipped, 0);
+        assert_eq!(total_1.total, 60);
+        assert_eq!(total_1.skipped, 0);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_generate_advice_stack_with_challenge() {
+        let h = Polynomial::new((0..512).map(Felt::new).collect());
+        let s2 = Polynomial::new((512..1024).map(Felt::new).collect());
+
+        let default_stack = generate_advice_stack_from_signature(h.clone(), s2.clone());
+        let rpo_stack = generate_advice_stack_with_challenge(h.clone(), s2.clone(), |elements| {
+            let digest = Hasher::hash_elements(elements);
+            (digest[0], digest[1])
+        });
+        assert_eq!(default_stack, rpo_stack);
+
+        let custom_stack =
+            generate_advice_stack_with_challenge(h, s2, |_| (Felt::new(1), Felt::new(2)));
+        assert_eq!(custom_stack[..2], [1, 2]);
+        assert_eq!(custom_stack[2..], default_stack[2..]);
+    }
+
+    #[tokio::test]
+    async fn test_assert_note_script() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+        let note = create_exact_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Public,
+            Felt::new(0),
+            Word::default(),
+        )
+        .unwrap();
+
+        assert!(assert_note_script(&note, WellKnownNote::P2ID.script_root()).is_ok());
+
+        let result = assert_note_script(&note, Word::default());
+        assert!(matches!(result, Err(ToolsError::NoteScriptMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_watch_notes() -> Result<(), Box<dyn std::error::Error>> {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let note_code = fs::read_to_string(Path::new("./masm/notes/increment_note.masm")).unwrap();
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code, library_path).unwrap();
+
+        let note = create_public_note(
+            &mut client,
+            note_code,
+            Some(library),
+            account.clone(),
+            None,
+            None,
+        )
+        .await?;
+
+        let mut notes = pin!(watch_notes(&mut client));
+        let note_id = tokio::time::timeout(std::time::Duration::from_secs(60), notes.next())
+            .await?
+            .unwrap();
+        assert_eq!(note_id, note.id());
+
+        delete_keystore_and_store(None).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_multi_output_request() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let public_output = OutputNoteSpec {
+            assets: vec![],
+            recipient: utils::build_p2id_recipient(target, Word::default()).unwrap(),
+            note_type: NoteType::Public,
+            tag: NoteTag::from_account_id(target),
+        };
+        let private_output = OutputNoteSpec {
+            assets: vec![],
+            recipient: utils::build_p2id_recipient(
+                target,
+                Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]),
+            )
+            .unwrap(),
+            note_type: NoteType::Private,
+            tag: NoteTag::from_account_id(target),
+        };
+
+        let request =
+            build_multi_output_request(sender, vec![public_output, private_output]).unwrap();
+        let notes = request.expected_output_own_notes();
+        assert_eq!(notes.len(), 2);
+        assert!(
+            notes
+                .iter()
+                .any(|note| note.metadata().note_type() == NoteType::Public)
+        );
+        assert!(
+            notes
+                .iter()
+                .any(|note| note.metadata().note_type() == NoteType::Private)
+        );
+
+        let result = build_multi_output_request(sender, vec![]);
+        assert!(matches!(result, Err(ToolsError::NoOutputs)));
+    }
+
+    #[tokio::test]
+    async fn test_build_full_request() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let input = create_exact_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Public,
+            Felt::new(0),
+            Word::default(),
+        )
+        .unwrap();
+        let output = create_exact_p2id_note(
+            target,
+            sender,
+            vec![],
+            NoteType::Public,
+            Felt::new(0),
+            Word::from([1, 2, 3, 4].map(Felt::new)),
+        )
+        .unwrap();
+
+        let request = build_full_request(FullTxSpec {
+            unauthenticated_inputs: vec![input.clone()],
+            own_outputs: vec![output.clone()],
+            ..FullTxSpec::default()
+        })
+        .unwrap();
+
+        assert_eq!(request.unauthenticated_input_notes().len(), 1);
+        assert_eq!(request.unauthenticated_input_notes()[0].id(), input.id());
+        let outputs = request.expected_output_own_notes();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].id(), output.id());
+    }
+
+    #[tokio::test]
+    async fn test_created_notes_of_type() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (sender, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (target, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let outputs = [NoteType::Public, NoteType::Private, NoteType::Public]
+            .into_iter()
+            .enumerate()
+            .map(|(i, note_type)| OutputNoteSpec {
+                assets: vec![],
+                recipient: utils::build_p2id_recipient(
+                    target.id(),
+                    Word::from([i as u64, 0, 0, 0].map(Felt::new)),
+                )
+                .unwrap(),
+                note_type,
+                tag: NoteTag::from_account_id(target.id()),
+            })
+            .collect();
+        let request = build_multi_output_request(sender.id(), outputs).unwrap();
+        let tx_result = client.new_transaction(sender.id(), request).await.unwrap();
+
+        let public_notes = created_notes_of_type(&tx_result, NoteType::Public);
+        let private_notes = created_notes_of_type(&tx_result, NoteType::Private);
+        assert_eq!(public_notes.len(), 2);
+        assert_eq!(private_notes.len(), 1);
+        assert!(
+            public_notes
+                .iter()
+                .all(|note| note.metadata().note_type() == NoteType::Public)
+        );
+        assert_eq!(private_notes[0].metadata().note_type(), NoteType::Private);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_replay_request() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let note =
+            create_p2id_note(&mut client, account.id(), target, vec![], NoteType::Public).unwrap();
+        let tx_script = create_tx_script("begin push.1 drop end".to_string(), None).unwrap();
+        let request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .custom_script(tx_script.clone())
+            .build()
+            .unwrap();
+
+        let bytes = serialize_tx_request(&request);
+        let decoded = deserialize_tx_request(&bytes).unwrap();
+        assert_eq!(
+            decoded.expected_output_own_notes()[0].serial_num(),
+            note.serial_num()
+        );
+        assert_eq!(decoded.script().unwrap().root(), tx_script.root());
+
+        let result = replay_request(&mut client, account.id(), &bytes).await;
+        assert!(result.is_ok());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_account_with_auth_keys() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let old_key = SecretKey::with_rng(client.rng());
+        let new_key = SecretKey::with_rng(client.rng());
+
+        let account =
+            create_account_with_auth_keys(&mut client, keystore.clone(), &[old_key.clone()])
+                .await
+                .unwrap();
+        assert_eq!(account.id().to_string().len(), 32);
+
+        let result =
+            create_account_with_auth_keys(&mut client, keystore, &[old_key, new_key]).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::UnsupportedAuthKeys { requested: 2, .. })
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_classify_endpoint() {
+        assert_eq!(
+            classify_endpoint(&Endpoint::localhost()),
+            Network::Localhost
+        );
+        assert_eq!(classify_endpoint(&Endpoint::testnet()), Network::Testnet);
+        assert_eq!(classify_endpoint(&Endpoint::devnet()), Network::Devnet);
+
+        let custom = Endpoint::new("https".to_string(), "node.example.com".to_string(), None);
+        assert_eq!(classify_endpoint(&custom), Network::Custom);
+    }
+
+    #[tokio::test]
+    async fn test_mint_to_recipient() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let recipient = utils::build_p2id_recipient(account.id(), Word::default()).unwrap();
+        let note = mint_to_recipient(
+            &mut client,
+            &faucet,
+            recipient.clone(),
+            100,
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(note.recipient().digest(), recipient.digest());
+        wait_for_note(&mut client, &note).await.unwrap();
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_compact_store() {
+        let store_path = "./compact_store.sqlite3";
+
+        let endpoint = Endpoint::localhost();
+        let client = instantiate_client(endpoint, Some(store_path))
+            .await
+            .unwrap();
+        drop(client);
+
+        assert!(compact_store(store_path).is_ok());
+
+        let missing = compact_store("./missing_store.sqlite3");
+        assert_eq!(missing.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+
+        delete_keystore_and_store(Some(store_path)).await;
+    }
+
+    #[tokio::test]
+    async fn test_public_tag() {
+        let tag = public_tag(3, 7, NoteExecutionMode::Local).unwrap();
+        assert_eq!(NoteTag::from(u32::from(tag)), tag);
+        assert_eq!(
+            tag,
+            NoteTag::for_public_use_case(3, 7, NoteExecutionMode::Local).unwrap()
+        );
+
+        assert!(public_tag(u16::MAX, 0, NoteExecutionMode::Local).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_account_tag() {
+        let account_id = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+        let tag = account_tag(account_id);
+        assert_eq!(NoteTag::from(u32::from(tag)), tag);
+        assert_eq!(tag, NoteTag::from_account_id(account_id));
+    }
+
+    #[tokio::test]
+    async fn test_plan_setup() {
+        let balances = vec![vec![10, 0], vec![30, 40]];
+        let plan = plan_setup(2, 2, &balances).unwrap();
+
+        assert_eq!(plan.num_accounts, 2);
+        assert_eq!(plan.num_faucets, 2);
+        assert_eq!(plan.mints.len(), 3);
+        assert_eq!(plan.tokens_per_faucet, vec![40, 40]);
+        assert_eq!(plan.num_transactions, 6);
+
+        let result = plan_setup(2, 3, &balances);
+        assert!(matches!(
+            result,
+            Err(ToolsError::InvalidBalanceMatrix { .. })
+        ));
+
+        let result = plan_setup(2, 1, &[vec![u64::MAX], vec![1]]);
+        assert!(matches!(
+            result,
+            Err(ToolsError::InvalidBalanceMatrix { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_consume_with_proof() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let minted_note = match mint_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note.clone(),
+            _ => panic!("Expected full minted note"),
+        };
+        wait_for_note(&mut client, &minted_note).await.unwrap();
+
+        let proof = client
+            .get_input_note(minted_note.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .inclusion_proof()
+            .cloned()
+            .unwrap();
+
+        let other_note = create_p2id_note(
+            &mut client,
+            faucet.id(),
+            account.id(),
+            vec![],
+            NoteType::Public,
+        )
+        .unwrap();
+        let result = consume_with_proof(&mut client, &account, other_note, proof.clone()).await;
+        assert!(matches!(result, Err(ToolsError::InvalidInclusionProof(_))));
+
+        let result = consume_with_proof(&mut client, &account, minted_note, proof).await;
+        assert!(result.is_ok());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_note_id() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+        let serial_num = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+
+        let note = create_exact_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Public,
+            Felt::new(0),
+            serial_num,
+        )
+        .unwrap();
+
+        assert_eq!(note_id(note.assets(), note.recipient()), note.id());
+    }
+
+    #[tokio::test]
+    async fn test_describe_note() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+        let faucet = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+
+        let asset = FungibleAsset::new(faucet, 25).unwrap();
+        let note = create_exact_p2id_note(
+            sender,
+            target,
+            vec![asset.into()],
+            NoteType::Public,
+            Felt::new(0),
+            Word::default(),
+        )
+        .unwrap();
+
+        let description = describe_note(&note);
+        assert!(description.contains(&note.id().to_hex()));
+        assert!(description.contains(&sender.to_string()));
+        assert!(description.contains(&format!("25 of faucet {faucet}")));
+        assert!(description.contains(&note.script().root().to_hex()));
+    }
+
+    #[tokio::test]
+    async fn test_nullifiers_for() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let notes: Vec<_> = (0..3)
+            .map(|i| {
+                let serial_num = Word::from([i, 0, 0, 0].map(Felt::new));
+                create_exact_p2id_note(
+                    sender,
+                    target,
+                    vec![],
+                    NoteType::Public,
+                    Felt::new(0),
+                    serial_num,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let nullifiers = nullifiers_for(&notes);
+        assert_eq!(nullifiers.len(), 3);
+        assert_eq!(nullifiers[0], notes[0].nullifier());
+        assert_ne!(nullifiers[0], nullifiers[1]);
+        assert_ne!(nullifiers[1], nullifiers[2]);
+        assert_ne!(nullifiers[0], nullifiers[2]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_transient() {
+        let mut attempts = 0;
+        let result = retry_on_transient(3, std::time::Duration::from_millis(1), || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt == 1 {
+                    Err(ClientError::RpcError(RpcError::ConnectionError(
+                        "node unavailable".into(),
+                    )))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+
+        let mut attempts = 0;
+        let result: Result<(), _> =
+            retry_on_transient(3, std::time::Duration::from_millis(1), || {
+                attempts += 1;
+                async {
+                    Err(ClientError::RpcError(RpcError::ExpectedDataMissing(
+                        "block".into(),
+                    )))
+                }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_notes_created_by() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let minted_note = match mint_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note.clone(),
+            _ => panic!("Expected full minted note"),
+        };
+        wait_for_note(&mut client, &minted_note).await.unwrap();
+
+        let notes = notes_created_by(&mut client, faucet.id()).await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id(), minted_note.id());
+        assert!(notes[0].is_committed());
+
+        let notes = notes_created_by(&mut client, account.id()).await.unwrap();
+        assert!(notes.is_empty());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_instantiate_client_with_rng_seed() {
+        let seed = [7_u8; 32];
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let mut account_ids = Vec::new();
+        for store_path in ["./seeded_store_1.sqlite3", "./seeded_store_2.sqlite3"] {
+            let config = ClientConfig::new(Endpoint::localhost())
+                .with_store_path(store_path)
+                .with_rng_seed(seed);
+            let mut client = instantiate_client_with_config(config).await.unwrap();
+
+            let (account, _) = create_basic_account(&mut client, keystore.clone())
+                .await
+                .unwrap();
+            account_ids.push(account.id());
+
+            delete_keystore_and_store(Some(store_path)).await;
+        }
+
+        assert_eq!(account_ids[0], account_ids[1]);
+    }
+
+    #[tokio::test]
+    async fn test_instantiate_client_without_debug_mode() {
+        let store_path = "./no_debug_store.sqlite3";
+        let config = ClientConfig::new(Endpoint::localhost())
+            .with_store_path(store_path)
+            .with_debug_mode(false);
+        assert!(!config.debug_mode);
+        assert!(ClientConfig::new(Endpoint::localhost()).debug_mode);
+
+        let mut client = instantiate_client_with_config(config).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+        submit_noop_transaction(&mut client, &account)
+            .await
+            .unwrap();
+
+        delete_keystore_and_store(Some(store_path)).await;
+    }
+
+    #[tokio::test]
+    async fn test_split_note() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (target_1, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (target_2, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let minted_note = match mint_exec.created_notes().get_note(0) {
+            OutputNote::Full(note) => note.clone(),
+            _ => panic!("Expected full minted note"),
+        };
+
+        let result = split_note(
+            &mut client,
+            &account,
+            minted_note.clone(),
+            &[(target_1.id(), 60), (target_2.id(), 50)],
+            NoteType::Public,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::InsufficientAmount {
+                available: 100,
+                requested: 110
+            })
+        ));
+
+        let notes = split_note(
+            &mut client,
+            &account,
+            minted_note,
+            &[(target_1.id(), 30), (target_2.id(), 70)],
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(
+            notes[0].metadata().tag(),
+            NoteTag::from_account_id(target_1.id())
+        );
+        assert_eq!(
+            notes[1].metadata().tag(),
+            NoteTag::from_account_id(target_2.id())
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_tools_assembler() {
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library(account_code.clone(), library_path).unwrap();
+
+        let library_1 = tools_assembler()
+            .assemble_library([NamedSource::new(library_path, account_code)])
+            .unwrap();
+
+        assert_eq!(library.digest(), library_1.digest());
+    }
+
+    #[tokio::test]
+    async fn test_sync_account_to_tip() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+
+        mint_from_faucet_for_account(&mut client, &account, &faucet, 100, None)
+            .await
+            .unwrap();
+
+        let height = sync_account_to_tip(
+            &mut client,
+            account.id(),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+        assert!(height > 0);
+
+        let record = client.get_account(account.id()).await.unwrap().unwrap();
+        assert_eq!(
+            record.account().vault().get_balance(faucet.id()).unwrap(),
+            100
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_faucet_with_metadata() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let metadata = FaucetMetadata {
+            symbol: "IMM".to_string(),
+            decimals: 6,
+            max_supply: 500_000,
+            code: CodeMutability::Immutable,
+        };
+        let faucet = create_faucet_with_metadata(&mut client, keystore.clone(), metadata)
+            .await
+            .unwrap();
+        assert_eq!(faucet.account_type(), AccountType::FungibleFaucet);
+
+        let metadata = FaucetMetadata {
+            code: CodeMutability::Updatable,
+            ..FaucetMetadata::default()
+        };
+        let result = create_faucet_with_metadata(&mut client, keystore, metadata).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::UnsupportedCodeMutability(
+                AccountType::FungibleFaucet
+            ))
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_faucet_from_config() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let config_path = "./faucet_config.toml";
+        fs::write(
+            config_path,
+            "symbol = \"CFG\"\ndecimals = 6\nmax_supply = 250000\n",
+        )
+        .unwrap();
+        let faucet = create_faucet_from_config(&mut client, keystore.clone(), config_path)
+            .await
+            .unwrap();
+        assert_eq!(faucet.account_type(), AccountType::FungibleFaucet);
+
+        fs::write(config_path, "symbol = \"CFG\"\ndecimals = 6\n").unwrap();
+        let result = create_faucet_from_config(&mut client, keystore.clone(), config_path).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::InvalidFaucetMetadata {
+                field: "max_supply",
+                ..
+            })
+        ));
+
+        fs::write(
+            config_path,
+            "symbol = \"CFG\"\ndecimals = 300\nmax_supply = 250000\n",
+        )
+        .unwrap();
+        let result = create_faucet_from_config(&mut client, keystore, config_path).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::InvalidFaucetMetadata {
+                field: "decimals",
+                ..
+            })
+        ));
+
+        fs::remove_file(config_path).unwrap();
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_advice_stack_len() {
+        let h = Polynomial::new((0..512).map(Felt::new).collect());
+        let s2 = Polynomial::new((512..1024).map(Felt::new).collect());
+
+        let advice_stack = generate_advice_stack_from_signature(h, s2);
+        assert_eq!(advice_stack.len(), advice_stack_len());
+        assert_eq!(advice_stack_len(), 2050);
+    }
+
+    #[tokio::test]
+    async fn test_signed_tx_script() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, key) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let message = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+        let public_key = Word::from(key.public_key());
+        let to_masm = |word: Word| {
+            word.iter()
+                .map(|felt| felt.as_int().to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+        };
+        let script_code = format!(
+            "
+            use.std::crypto::dsa::rpo_falcon512
+
+            begin
+                push.{message}
+                push.{public_key}
+                exec.rpo_falcon512::verify
+            end
+            ",
+            message = to_masm(message),
+            public_key = to_masm(public_key),
+        );
+
+        let (tx_script, advice_inputs) =
+            signed_tx_script(script_code, None, &key, message).unwrap();
+        let result = client
+            .execute_program(account.id(), tx_script, advice_inputs, Default::default())
+            .await;
+        assert!(result.is_ok(), "signature verification failed: {result:?}");
+
+        let result = signed_tx_script("begin push.1".to_string(), None, &key, message);
+        assert!(matches!(result, Err(ToolsError::InvalidScript(_))));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_latest_block_header() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+
+        let block_header = latest_block_header(&mut client).await.unwrap();
+        assert!(block_header.block_num().as_u32() > 0);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_funded_account() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+
+        let (account, _) =
+            create_funded_account(&mut client, keystore, &faucet, 250, NoteType::Public)
+                .await
+                .unwrap();
+        assert_eq!(account.vault().get_balance(faucet.id()).unwrap(), 250);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_find_orphaned_keys() {
+        let endpoint = Endpoint::localhost();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let mut client = instantiate_client(endpoint.clone(), None).await.unwrap();
+        let (_, key) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+
+        let orphaned = find_orphaned_keys(&mut client, &keystore).await.unwrap();
+        assert!(!orphaned.contains(&key.public_key()));
+        drop(client);
+
+        tokio::fs::remove_file("./store.sqlite3").await.unwrap();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+
+        let orphaned = find_orphaned_keys(&mut client, &keystore).await.unwrap();
+        assert!(orphaned.contains(&key.public_key()));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_consume_notes_deadline() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (other_account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let mut notes = Vec::new();
+        for _ in 0..2 {
+            let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+            let mint_req = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+                .unwrap();
+            let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+            client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+            match mint_exec.created_notes().get_note(0) {
+                OutputNote::Full(note) => notes.push(note.clone()),
+                _ => panic!("Expected full minted note"),
+            }
+        }
+
+        // a P2ID note for another account cannot be consumed by `account`
+        let unconsumable = create_p2id_note(
+            &mut client,
+            faucet.id(),
+            other_account.id(),
+            vec![],
+            NoteType::Public,
+        )
+        .unwrap();
+        notes.insert(1, unconsumable.clone());
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(300);
+        let (consumed, failed) =
+            consume_notes_deadline(&mut client, &account, notes.clone(), deadline).await;
+
+        assert_eq!(consumed, vec![notes[0].id(), notes[2].id()]);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, unconsumable.id());
+        assert!(matches!(
+            failed[0].1,
+            ToolsError::NoteNotConsumable(id) if id == unconsumable.id()
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_instantiate_client_in_memory() {
+        let endpoint = Endpoint::localhost();
+        let (mut client, keystore) = instantiate_client_in_memory(endpoint).await.unwrap();
+
+        let (account, key) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+
+        assert!(client.get_account(account.id()).await.unwrap().is_some());
+        let keys = keystore.secret_keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        let AuthSecretKey::RpoFalcon512(stored) = &keys[0];
+        assert_eq!(stored.public_key(), key.public_key());
+        assert!(tokio::fs::metadata("./store.sqlite3").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_and_submit_with_authenticator() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint.clone(), None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        // the faucet key only lives in the in-memory keystore, not in `./keystore`
+        let signer = InMemoryKeyStore::new();
+        let faucet = create_basic_faucet(&mut client, signer.clone())
+            .await
+            .unwrap();
+        let (account, _) = create_basic_account(&mut client, signer.clone())
+            .await
+            .unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 50).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        drop(client);
+
+        let tx_result = execute_and_submit_with_authenticator(
+            ClientConfig::new(endpoint),
+            signer,
+            faucet.id(),
+            mint_req,
+        )
+        .await
+        .unwrap();
+        assert_eq!(tx_result.created_notes().num_notes(), 1);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_can_consume() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (other_account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        // the P2ID note input is the target account ID, which only `other_account` satisfies
+        let note = create_p2id_note(
+            &mut client,
+            faucet.id(),
+            other_account.id(),
+            vec![],
+            NoteType::Public,
+        )
+        .unwrap();
+
+        assert!(!can_consume(&mut client, &account, &note).await.unwrap());
+        assert!(
+            can_consume(&mut client, &other_account, &note)
+                .await
+                .unwrap()
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_private_note_with_details() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint.clone(), None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let recipient_store = "./private_note_recipient.sqlite3";
+        let mut recipient_client = instantiate_client(endpoint, Some(recipient_store))
+            .await
+            .unwrap();
+        recipient_client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (creator, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (recipient, _) = create_basic_account(&mut recipient_client, keystore)
+            .await
+            .unwrap();
+
+        let note_code = fs::read_to_string(Path::new("./masm/notes/P2ID.masm")).unwrap();
+        let note_inputs = NoteInputs::new(vec![
+            recipient.id().suffix(),
+            recipient.id().prefix().as_felt(),
+        ])
+        .unwrap();
+
+        let (note, blob) = create_private_note_with_details(
+            &mut client,
+            note_code,
+            None,
+            creator,
+            None,
+            Some(note_inputs),
+        )
+        .await
+        .unwrap();
+        assert_eq!(note.metadata().note_type(), NoteType::Private);
+
+        // the recipient only learns about the note through the blob
+        import_note_bytes(&mut recipient_client, &blob)
+            .await
+            .unwrap();
+
+        let imported: Note = recipient_client
+            .get_input_note(note.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let consume_req = TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(imported, None)])
+            .build()
+            .unwrap();
+        let tx_result =
+            execute_and_submit(&mut recipient_client, recipient.id(), consume_req, false)
+                .await
+                .unwrap();
+        assert_eq!(
+            tx_result.executed_transaction().input_notes().num_notes(),
+            1
+        );
+
+        delete_keystore_and_store(Some(recipient_store)).await;
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_import_note() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (sender, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let note = create_p2id_note(
+            &mut client,
+            sender.id(),
+            account.id(),
+            vec![],
+            NoteType::Public,
+        )
+        .unwrap();
+        assert!(client.get_input_note(note.id()).await.unwrap().is_none());
+
+        import_note(&mut client, note.clone()).await.unwrap();
+
+        let imported: Note = client
+            .get_input_note(note.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(imported.id(), note.id());
+
+        let consume_req = TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(imported, None)])
+            .build()
+            .unwrap();
+        execute_and_submit(&mut client, account.id(), consume_req, false)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            import_note_bytes(&mut client, &[0xff; 4]).await,
+            Err(ToolsError::Deserialization(_))
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_submit_transaction_timed() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        let expected_id = mint_exec.executed_transaction().id();
+
+        let (tx_id, elapsed) = submit_transaction_timed(&mut client, mint_exec)
+            .await
+            .unwrap();
+        assert_eq!(tx_id, expected_id);
+        assert!(!elapsed.is_zero());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_submit_noop_transaction() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let before = snapshot_account(&mut client, account.id()).await.unwrap();
+        submit_noop_transaction(&mut client, &account)
+            .await
+            .unwrap();
+        let after = snapshot_account(&mut client, account.id()).await.unwrap();
+
+        let diff = diff_snapshots(&before, &after);
+        assert_eq!(diff.nonce_delta, 1);
+        assert!(diff.balance_changes.is_empty());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_faucet_mint_history() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        for amount in [15, 40] {
+            let asset = FungibleAsset::new(faucet.id(), amount).unwrap();
+            let mint_req = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+                .unwrap();
+            let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+            client.submit_transaction(mint_exec).await.unwrap();
+        }
+        client.sync_state().await.unwrap();
+
+        let history = faucet_mint_history(&mut client, faucet.id()).await.unwrap();
+        let mut amounts: Vec<u64> = history.iter().map(|mint| mint.amount).collect();
+        amounts.sort();
+        assert_eq!(amounts, vec![15, 40]);
+        assert!(history.iter().all(|mint| mint.target == Some(account.id())));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_balances() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (alice, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (bob, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        // mint to both accounts before either consumes
+        let mut notes = Vec::new();
+        for (account, amount) in [(&alice, 30), (&bob, 70)] {
+            let asset = FungibleAsset::new(faucet.id(), amount).unwrap();
+            let mint_req = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+                .unwrap();
+            let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+            client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+            match mint_exec.created_notes().get_note(0) {
+                OutputNote::Full(note) => notes.push(note.clone()),
+                _ => panic!("Expected full minted note"),
+            }
+        }
+        consume_for_accounts(
+            &mut client,
+            &[
+                (&alice, vec![notes[0].clone()]),
+                (&bob, vec![notes[1].clone()]),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let expectations = [(alice.id(), faucet.id(), 30), (bob.id(), faucet.id(), 70)];
+        wait_for_balances(
+            &mut client,
+            &expectations,
+            std::time::Duration::from_secs(120),
+        )
+        .await
+        .unwrap();
+
+        let unreachable = [(alice.id(), faucet.id(), 30), (bob.id(), faucet.id(), 71)];
+        let result =
+            wait_for_balances(&mut client, &unreachable, std::time::Duration::from_secs(1)).await;
+        match result {
+            Err(ToolsError::BalancesNotReached(unmet)) => {
+                assert_eq!(unmet, vec![(bob.id(), faucet.id(), 71)])
+            }
+            other => panic!("expected unmet balances, got {other:?}"),
+        }
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_multi_asset_swap() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let offered_faucet = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet_b = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet_c = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        client.sync_state().await.unwrap();
+
+        let (maker, _) = create_funded_account(
+            &mut client,
+            keystore.clone(),
+            &offered_faucet,
+            100,
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        let (taker, _) =
+            create_funded_account(&mut client, keystore, &faucet_b, 50, NoteType::Public)
+                .await
+                .unwrap();
+        mint_from_faucet_for_account(&mut client, &taker, &faucet_c, 50, None)
+            .await
+            .unwrap();
+
+        let offered: Asset = FungibleAsset::new(offered_faucet.id(), 40).unwrap().into();
+        let requested: Vec<Asset> = vec![
+            FungibleAsset::new(faucet_b.id(), 10).unwrap().into(),
+            FungibleAsset::new(faucet_c.id(), 20).unwrap().into(),
+        ];
+
+        let result =
+            create_multi_asset_swap(&mut client, &maker, offered, vec![], NoteType::Public).await;
+        assert!(matches!(result, Err(ToolsError::NoRequestedAssets)));
+        let duplicated = vec![requested[0], requested[0]];
+        let result =
+            create_multi_asset_swap(&mut client, &maker, offered, duplicated, NoteType::Public)
+                .await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::DuplicateRequestedFaucet(_))
+        ));
+
+        let (swap_note, payback) =
+            create_multi_asset_swap(&mut client, &maker, offered, requested, NoteType::Public)
+                .await
+                .unwrap();
+
+        let consume_req = TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(swap_note, None)])
+            .build()
+            .unwrap();
+        let tx_result = execute_and_submit(&mut client, taker.id(), consume_req, false)
+            .await
+            .unwrap();
+        client.sync_state().await.unwrap();
+
+        // a single payback note carries both requested assets back to the maker
+        let created = tx_result.created_notes();
+        assert_eq!(created.num_notes(), 1);
+        assert_eq!(created.get_note(0).id(), payback.id());
+
+        let report = balance_report(&mut client, &[taker], &[offered_faucet, faucet_b, faucet_c])
+            .await
+            .unwrap();
+        assert_eq!(report, vec![vec![40, 40, 30]]);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_rotate_auth_key() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, old_key) = create_rotatable_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+
+        let new_key = SecretKey::with_rng(client.rng());
+        rotate_auth_key(&mut client, keystore.clone(), &account, new_key.clone())
+            .await
+            .unwrap();
+
+        // the auth procedure checks the key in slot 0, so this transaction is signed with it
+        let snapshot = snapshot_account(&mut client, account.id()).await.unwrap();
+        assert_eq!(snapshot.storage[0], Word::from(new_key.public_key()));
+        assert_ne!(snapshot.storage[0], Word::from(old_key.public_key()));
+        assert_eq!(snapshot.storage[1], Word::default());
+        submit_noop_transaction(&mut client, &account)
+            .await
+            .unwrap();
+
+        let immutable = AccountBuilder::new([3; 32])
+            .account_type(AccountType::RegularAccountImmutableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_auth_component(auth::NoAuth)
+            .with_component(BasicWallet)
+            .build()
+            .unwrap()
+            .0;
+        let result = rotate_auth_key(&mut client, keystore.clone(), &immutable, new_key).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::ImmutableAccountCode(id)) if id == immutable.id()
+        ));
+
+        // a basic account lacks the rotatable auth procedures, so the key is not stored
+        let (basic, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let unused_key = SecretKey::with_rng(client.rng());
+        let result =
+            rotate_auth_key(&mut client, keystore.clone(), &basic, unused_key.clone()).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::MissingProcedure { account_id, .. }) if account_id == basic.id()
+        ));
+        let unused_public_key = Word::from(unused_key.public_key());
+        let keys = keystore.secret_keys().unwrap();
+        assert!(!keys.iter().any(
+            |AuthSecretKey::RpoFalcon512(key)| Word::from(key.public_key()) == unused_public_key
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_accounts_mixed() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let specs = [AccountStorageMode::Public, AccountStorageMode::Private];
+        let accounts = create_accounts_mixed(&mut client, keystore, &specs)
+            .await
+            .unwrap();
+
+        assert_eq!(accounts.len(), 2);
+        assert!(accounts[0].0.is_public());
+        assert!(!accounts[1].0.is_public());
+        assert_ne!(accounts[0].1.public_key(), accounts[1].1.public_key());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_consume_notes_fifo() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        // wait for each note to be committed so that every note lands in a later block
+        let mut minted = Vec::new();
+        for _ in 0..3 {
+            let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+            let mint_req = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+                .unwrap();
+            let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+            client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+            let note = created_notes_of_type(&mint_exec, NoteType::Public).remove(0);
+            wait_for_note(&mut client, &note).await.unwrap();
+            minted.push(note.id());
+        }
+
+        let consumed = consume_notes_fifo(&mut client, &account, 2).await.unwrap();
+        assert_eq!(consumed, minted[..2]);
+
+        let consumed = consume_notes_fifo(&mut client, &account, 2).await.unwrap();
+        assert_eq!(consumed, minted[2..]);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_account_exists_on_chain() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let accounts = create_accounts_mixed(
+            &mut client,
+            keystore,
+            &[AccountStorageMode::Public, AccountStorageMode::Private],
+        )
+        .await
+        .unwrap();
+        let (public_account, _) = &accounts[0];
+        let (private_account, _) = &accounts[1];
+
+        assert_eq!(
+            account_exists_on_chain(&mut client, public_account.id())
+                .await
+                .unwrap(),
+            AccountOnChainStatus::NotFound
+        );
+
+        // the first transaction records the account on-chain
+        submit_noop_transaction(&mut client, public_account)
+            .await
+            .unwrap();
+        assert_eq!(
+            account_exists_on_chain(&mut client, public_account.id())
+                .await
+                .unwrap(),
+            AccountOnChainStatus::Exists
+        );
+
+        assert_eq!(
+            account_exists_on_chain(&mut client, private_account.id())
+                .await
+                .unwrap(),
+            AccountOnChainStatus::Unknown
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_sweep_account() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let balances = vec![vec![10, 20], vec![0, 0]];
+        let (accounts, faucets) = setup_accounts_and_faucets(&mut client, keystore, 2, 2, balances)
+            .await
+            .unwrap();
+
+        let notes = sweep_account(
+            &mut client,
+            &accounts[0],
+            accounts[1].id(),
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].assets().num_assets(), 2);
+
+        wait_for_note(&mut client, &notes[0]).await.unwrap();
+        let consume_req = TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(notes[0].clone(), None)])
+            .build()
+            .unwrap();
+        let tx_exec = client
+            .new_transaction(accounts[1].id(), consume_req)
+            .await
+            .unwrap();
+        client.submit_transaction(tx_exec).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let report = balance_report(&mut client, &accounts, &faucets)
+            .await
+            .unwrap();
+        assert_eq!(report, vec![vec![0, 0], vec![10, 20]]);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_note_with_advice_map() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        // the script loads the value stored under the key and checks it
+        let note_code = "
+            begin
+                push.7.7.7.7
+                adv.push_mapval
+                adv_push.1
+                push.42
+                assert_eq
+                dropw
+            end
+        "
+        .to_string();
+        let key = Word::from([Felt::new(7); 4]);
+
+        let note = create_public_note(&mut client, note_code, None, account.clone(), None, None)
+            .await
+            .unwrap();
+
+        let advice_map = vec![(key, vec![Felt::new(42)])];
+        consume_note_with_advice(&mut client, account.id(), &note, advice_map)
+            .await
+            .unwrap();
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_component_storage_root() {
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let value = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+        let storage_slots = storage_slots_from(&[(0, value)]).unwrap();
+        let component = compile_account_component(account_code, storage_slots).unwrap();
+
+        let predicted = component_storage_root(&component);
+
+        let (account, _) = AccountBuilder::new([7_u8; 32])
+            .account_type(AccountType::RegularAccountImmutableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_component(component)
+            .with_component(BasicWallet)
+            .with_auth_component(auth::NoAuth)
+            .build()
+            .unwrap();
+        assert_eq!(predicted, account.storage().commitment());
+    }
+
+    #[tokio::test]
+    async fn test_create_faucets() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let specs = vec![
+            ("AAA".to_string(), 2, 1_000),
+            ("BBB".to_string(), 6, 500_000),
+            ("CCC".to_string(), 8, 1_000_000),
+        ];
+        let faucets = create_faucets(&mut client, keystore.clone(), &specs)
+            .await
+            .unwrap();
+        assert_eq!(faucets.len(), 3);
+
+        for (faucet, (symbol, decimals, max_supply)) in faucets.into_iter().zip(&specs) {
+            let faucet = BasicFungibleFaucet::try_from(faucet).unwrap();
+            assert_eq!(faucet.symbol(), TokenSymbol::new(symbol).unwrap());
+            assert_eq!(faucet.decimals(), *decimals);
+            assert_eq!(faucet.max_supply(), Felt::new(*max_supply));
+        }
+
+        let duplicates = vec![("DUP".to_string(), 8, 1_000), ("DUP".to_string(), 8, 1_000)];
+        let result = create_faucets(&mut client, keystore, &duplicates).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::DuplicateFaucetSymbol(symbol)) if symbol == "DUP"
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_await_created_note() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let timeout = std::time::Duration::from_secs(120);
+        let record = await_created_note(&mut client, &mint_exec, 0, timeout)
+            .await
+            .unwrap();
+        assert_eq!(record.id(), mint_exec.created_notes().get_note(0).id());
+        assert!(record.inclusion_proof().is_some());
+
+        let result = await_created_note(&mut client, &mint_exec, 1, timeout).await;
+        assert!(matches!(result, Err(ToolsError::NoMatchingNotes)));
+
+        let consume_req = TransactionRequestBuilder::new()
+            .authenticated_input_notes([(record.id(), None)])
+            .build()
+            .unwrap();
+        let tx_exec = client
+            .new_transaction(account.id(), consume_req)
+            .await
+            .unwrap();
+        client.submit_transaction(tx_exec).await.unwrap();
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_rpc_limiter_bounds_concurrent_calls() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limiter = RpcLimiter::new(Some(3));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        // each mock call records how many calls are running alongside it
+        let calls = (0..20).map(|_| {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            limiter.run(async move {
+                let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            })
+        });
+        futures::future::join_all(calls).await;
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+
+        let config = ClientConfig::new(Endpoint::localhost()).with_max_concurrent_rpcs(3);
+        assert_eq!(config.max_concurrent_rpcs, Some(3));
+        assert_eq!(
+            ClientConfig::new(Endpoint::localhost()).max_concurrent_rpcs,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_account_and_advance_nonce() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, nonce) = create_account_and_advance_nonce(&mut client, keystore, 3)
+            .await
+            .unwrap();
+        assert_eq!(nonce, 3);
+        assert_eq!(account.nonce().as_int(), 3);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_check_transfer_feasible() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (sender, _) = create_funded_account(
+            &mut client,
+            keystore.clone(),
+            &faucet,
+            100,
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        let (target, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        check_transfer_feasible(&mut client, &sender, target.id(), faucet.id(), 100)
+            .await
+            .unwrap();
+
+        let result =
+            check_transfer_feasible(&mut client, &sender, target.id(), faucet.id(), 101).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::InsufficientAmount {
+                available: 100,
+                requested: 101
+            })
+        ));
+
+        let result =
+            check_transfer_feasible(&mut client, &sender, target.id(), faucet.id(), 0).await;
+        assert!(matches!(result, Err(ToolsError::ZeroAmount)));
+
+        // a public account that was never added to the client nor deployed
+        let (unknown, _) = AccountBuilder::new([9_u8; 32])
+            .account_type(AccountType::RegularAccountImmutableCode)
+            .storage_mode(AccountStorageMode::Public)
+            .with_component(BasicWallet)
+            .with_auth_component(auth::NoAuth)
+            .build()
+            .unwrap();
+        let result =
+            check_transfer_feasible(&mut client, &sender, unknown.id(), faucet.id(), 10).await;
+        assert!(matches!(result, Err(ToolsError::TargetNotFound(id)) if id == unknown.id()));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_format_tx_error() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let script_code = "begin\n    push.1 push.2\n    assert_eq.err=\"values differ\"\nend\n";
+        let tx_script = create_tx_script(script_code.to_string(), None).unwrap();
+        let tx_req = TransactionRequestBuilder::new()
+            .custom_script(tx_script)
+            .build()
+            .unwrap();
+
+        let err = client
+            .new_transaction(account.id(), tx_req)
+            .await
+            .unwrap_err();
+        let formatted = format_tx_error(&err);
+
+        assert!(formatted.contains("values differ"), "{formatted}");
+        // the failing assertion is on the third line of the script
+        assert!(formatted.contains(":3:"), "{formatted}");
+        assert!(formatted.contains("assert_eq"), "{formatted}");
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_serial() {
+        assert_eq!(deterministic_serial(42, 0), deterministic_serial(42, 0));
+        assert_ne!(deterministic_serial(42, 0), deterministic_serial(42, 1));
+        assert_ne!(deterministic_serial(42, 0), deterministic_serial(43, 0));
+
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+        let note = |serial_num| {
+            create_exact_p2id_note(
+                sender,
+                target,
+                vec![],
+                NoteType::Public,
+                Felt::new(0),
+                serial_num,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            note(deterministic_serial(42, 0)).id(),
+            note(deterministic_serial(42, 0)).id()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_stores() {
+        let endpoint = Endpoint::localhost();
+        let target_path = "./merge_target.sqlite3";
+        let source_paths = ["./merge_source_a.sqlite3", "./merge_source_b.sqlite3"];
+
+        let mut account_ids = Vec::new();
+        for source_path in source_paths {
+            let mut client = instantiate_client(endpoint.clone(), Some(source_path))
+                .await
+                .unwrap();
+            let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+            let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+            account_ids.push(account.id());
+        }
+        drop(
+            instantiate_client(endpoint.clone(), Some(target_path))
+                .await
+                .unwrap(),
+        );
+
+        merge_stores(target_path, &source_paths).unwrap();
+        // merging again only finds duplicates
+        merge_stores(target_path, &source_paths).unwrap();
+
+        let client = instantiate_client(endpoint, Some(target_path))
+            .await
+            .unwrap();
+        let merged: Vec<AccountId> = client
+            .get_account_headers()
+            .await
+            .unwrap()
+            .iter()
+            .map(|(header, _)| header.id())
+            .collect();
+        assert_eq!(merged.len(), 2);
+        assert!(account_ids.iter().all(|id| merged.contains(id)));
+        drop(client);
+
+        assert!(merge_stores(target_path, &["./missing_store.sqlite3"]).is_err());
+
+        // a source whose schema does not match the target's is rejected
+        let bogus_path = "./merge_bogus.sqlite3";
+        rusqlite::Connection::open(bogus_path)
+            .unwrap()
+            .execute_batch("CREATE TABLE accounts (bogus INTEGER);")
+            .unwrap();
+        let result = merge_stores(target_path, &[bogus_path, source_paths[0]]);
+        assert!(matches!(
+            result,
+            Err(ToolsError::StoreSchemaMismatch { .. })
+        ));
+        merge_stores(target_path, &[source_paths[0]]).unwrap();
+        fs::remove_file(bogus_path).unwrap();
+
+        // a source holding a different version of an account the target has conflicts
+        let conflict_path = "./merge_conflict.sqlite3";
+        fs::copy(source_paths[0], conflict_path).unwrap();
+        let connection = rusqlite::Connection::open(conflict_path).unwrap();
+        let column: String = connection
+            .query_row(
+                "SELECT name FROM pragma_table_info('accounts') WHERE pk = 0 LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        connection
+            .execute(
+                &format!("UPDATE accounts SET {column} = randomblob(32)"),
+                [],
+            )
+            .unwrap();
+        drop(connection);
+        let result = merge_stores(target_path, &[conflict_path]);
+        assert!(matches!(
+            result,
+            Err(ToolsError::StoreMergeConflict { table, .. }) if table == "accounts"
+        ));
+        fs::remove_file(conflict_path).unwrap();
+
+        for source_path in source_paths {
+            delete_keystore_and_store(Some(source_path)).await;
+        }
+        delete_keystore_and_store(Some(target_path)).await;
+    }
+
+    #[tokio::test]
+    async fn test_estimate_script_cycles() {
+        let script_code = "
+            begin
+                repeat.100
+                    push.1 add
+                end
+                drop
+            end
+        ";
+        let cycles = estimate_script_cycles(script_code, None, &[Felt::new(5)]).unwrap();
+        // 200 cycles for the loop body plus the program's control flow overhead
+        assert!(
+            (200..300).contains(&cycles),
+            "unexpected cycle count {cycles}"
+        );
+
+        assert!(matches!(
+            estimate_script_cycles("begin not_an_instruction end", None, &[]),
+            Err(ToolsError::InvalidScript(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_tx_script_cycles() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let (account, library) = create_custom_account(
+            &mut client,
+            account_code,
+            "external_contract::counter_contract",
+            vec![StorageSlot::empty_value()],
+            &AccountLibraries::new(),
+        )
+        .await
+        .unwrap();
+
+        // the increment script reads and writes account storage through the kernel
+        let script_code =
+            fs::read_to_string(Path::new("./masm/scripts/increment_script.masm")).unwrap();
+        let tx_script = create_tx_script(script_code, Some(library)).unwrap();
+        let cycles = estimate_tx_script_cycles(&mut client, account.id(), tx_script)
+            .await
+            .unwrap();
+        assert!(
+            (100..20_000).contains(&cycles),
+            "unexpected cycle count {cycles}"
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_watch_account() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint.clone(), None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        // deploy the account so that a second client can import it
+        submit_noop_transaction(&mut client, &account)
+            .await
+            .unwrap();
+
+        let watcher_store = "./watch_account_store.sqlite3";
+        let mut watcher = instantiate_client(endpoint, Some(watcher_store))
+            .await
+            .unwrap();
+        watcher.import_account_by_id(account.id()).await.unwrap();
+        let before = snapshot_account(&mut watcher, account.id()).await.unwrap();
+
+        let mut snapshots = pin!(watch_account(&mut watcher, account.id()));
+        let (snapshot, minted) = tokio::join!(
+            tokio::time::timeout(std::time::Duration::from_secs(120), snapshots.next()),
+            mint_from_faucet_for_account(&mut client, &account, &faucet, 100, None),
+        );
+        minted.unwrap();
+        let snapshot = snapshot.unwrap().unwrap();
+
+        assert_eq!(snapshot.account_id, account.id());
+        assert!(snapshot.nonce > before.nonce);
+        assert_eq!(snapshot.balances.get(&faucet.id()), Some(&100));
+
+        delete_keystore_and_store(Some(watcher_store)).await;
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_safe_note_assets() {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        let assets = vec![
+            FungibleAsset::new(faucet_id, 40).unwrap().into(),
+            FungibleAsset::new(faucet_id, 60).unwrap().into(),
+        ];
+        assert!(NoteAssets::new(assets.clone()).is_err());
+
+        let note_assets = safe_note_assets(assets).unwrap();
+        assert_eq!(note_assets.num_assets(), 1);
+        assert_eq!(
+            note_assets.iter().next(),
+            Some(&FungibleAsset::new(faucet_id, 100).unwrap().into())
+        );
+
+        let max = FungibleAsset::new(faucet_id, FungibleAsset::MAX_AMOUNT).unwrap();
+        let one = FungibleAsset::new(faucet_id, 1).unwrap();
+        let result = safe_note_assets(vec![max.into(), one.into()]);
+        assert!(matches!(result, Err(ToolsError::AmountOverflow(id)) if id == faucet_id));
+    }
+
+    #[tokio::test]
+    async fn test_instantiate_client_for() {
+        for network in [Network::Localhost, Network::Devnet, Network::Testnet] {
+            assert_eq!(classify_endpoint(&network.endpoint().unwrap()), network);
+        }
+        assert!(Network::Custom.endpoint().is_none());
+
+        // building the client does not contact the node
+        let store_path = "./testnet_store.sqlite3";
+        let client = instantiate_client_for(Network::Testnet, Some(store_path)).await;
+        assert!(client.is_ok());
+        drop(client);
+
+        let result = instantiate_client_for(Network::Custom, Some(store_path)).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::NoCanonicalEndpoint(Network::Custom))
+        ));
+
+        delete_keystore_and_store(Some(store_path)).await;
+    }
+
+    #[tokio::test]
+    async fn test_keystore_has_key_for() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        assert!(keystore_has_key_for(&keystore, &account));
+
+        let other_keystore = InMemoryKeyStore::new();
+        assert!(!keystore_has_key_for(&other_keystore, &account));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_export_and_verify_transaction_proof() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let asset = FungibleAsset::new(faucet.id(), 100).unwrap();
+        let mint_req = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+            .unwrap();
+        let mint_exec = client.new_transaction(faucet.id(), mint_req).await.unwrap();
+        client.submit_transaction(mint_exec.clone()).await.unwrap();
+
+        let proof_path = "./mint_proof.bin";
+        export_transaction_proof(&mint_exec, proof_path)
+            .await
+            .unwrap();
+        let tx_id = verify_transaction_proof(proof_path).unwrap();
+        assert_eq!(tx_id, mint_exec.executed_transaction().id());
+
+        // a corrupted proof does not deserialize or verify
+        let mut bytes = std::fs::read(proof_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        std::fs::write(proof_path, bytes).unwrap();
+        assert!(verify_transaction_proof(proof_path).is_err());
+
+        std::fs::remove_file(proof_path).unwrap();
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_mint_series() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+
+        let mut targets = Vec::new();
+        for amount in 1..=5 {
+            let (account, _) = create_basic_account(&mut client, keystore.clone())
+                .await
+                .unwrap();
+            targets.push((account.id(), amount * 10));
+        }
+
+        let notes = mint_series(&mut client, &faucet, &targets, NoteType::Public)
+            .await
+            .unwrap();
+        assert_eq!(notes.len(), 5);
+        for (note, (_, amount)) in notes.iter().zip(&targets) {
+            assert_eq!(
+                note.assets().iter().next(),
+                Some(&FungibleAsset::new(faucet.id(), *amount).unwrap().into())
+            );
+        }
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_note_from_script() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (creator, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let (target, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let script = WellKnownNote::P2ID.script();
+        let inputs =
+            NoteInputs::new(vec![target.id().suffix(), target.id().prefix().as_felt()]).unwrap();
+        let note = create_note_from_script(
+            &mut client,
+            script,
+            &creator,
+            None,
+            Some(inputs),
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        assert_eq!(note.script().root(), WellKnownNote::P2ID.script_root());
+
+        wait_for_note(&mut client, &note).await.unwrap();
+        let consume_req = TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(note, None)])
+            .build()
+            .unwrap();
+        let tx_exec = client
+            .new_transaction(target.id(), consume_req)
+            .await
+            .unwrap();
+        client.submit_transaction(tx_exec).await.unwrap();
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_library_diagnostic() {
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let library = create_library_diagnostic(account_code.clone(), library_path).unwrap();
+        assert_eq!(
+            library.digest(),
+            create_library(account_code, library_path).unwrap().digest()
+        );
+
+        let invalid_code = "export.broken\n    push.1\n    not_an_instruction\nend\n";
+        let diagnostics =
+            create_library_diagnostic(invalid_code.to_string(), library_path).unwrap_err();
+        assert!(!diagnostics.is_empty());
+
+        let label = diagnostics
+            .iter()
+            .flat_map(|diagnostic| &diagnostic.labels)
+            .next()
+            .expect("the parser points at the invalid instruction");
+        assert_eq!(label.line, Some(3));
+        assert_eq!(
+            &invalid_code[label.offset..label.offset + label.len],
+            "not_an_instruction"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consume_and_assert_delta() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+
+        let notes = mint_series(
+            &mut client,
+            &faucet,
+            &[(account.id(), 50), (account.id(), 50)],
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+
+        consume_and_assert_delta(&mut client, &account, notes[0].clone(), 50, faucet.id())
+            .await
+            .unwrap();
+
+        let note = notes[1].clone();
+        let result = consume_and_assert_delta(&mut client, &account, note, 40, faucet.id()).await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::UnexpectedDelta {
+                expected: 40,
+                actual: 50,
+                ..
+            })
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_balances_to_csv() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+        let balances = vec![vec![10, 0], vec![0, 25]];
+        let (accounts, faucets) = setup_accounts_and_faucets(&mut client, keystore, 2, 2, balances)
+            .await
+            .unwrap();
+
+        let mut csv = Vec::new();
+        balances_to_csv(&mut client, &accounts, &faucets, &mut csv)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "account_id,MIDA,MIDB");
+        assert_eq!(lines[1], format!("{},10,0", accounts[0].id().to_hex()));
+        assert_eq!(lines[2], format!("{},0,25", accounts[1].id().to_hex()));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_sync_controller() {
+        let endpoint = Endpoint::localhost();
+        let client = instantiate_client(endpoint, None).await.unwrap();
+        let mut controller = SyncController::new(client);
+        assert!(matches!(
+            controller.sync_state().await.unwrap(),
+            SyncStatus::Done(_)
+        ));
+
+        controller.pause();
+        assert!(controller.is_paused());
+        let height = controller.client().get_sync_height().await.unwrap();
+
+        // let the chain advance; a paused controller must not pick up the new blocks
+        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+        assert!(matches!(
+            controller.sync_state().await.unwrap(),
+            SyncStatus::Paused
+        ));
+        assert_eq!(controller.client().get_sync_height().await.unwrap(), height);
+
+        controller.resume();
+        assert!(matches!(
+            controller.sync_state().await.unwrap(),
+            SyncStatus::Done(_)
+        ));
+        assert!(controller.client().get_sync_height().await.unwrap() > height);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_assert_p2id_target() {
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let note = create_exact_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Public,
+            Felt::new(0),
+            Word::default(),
+        )
+        .unwrap();
+
+        assert!(assert_p2id_target(&note, target).is_ok());
+        assert!(matches!(
+            assert_p2id_target(&note, sender),
+            Err(ToolsError::RecipientMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_falcon_keypair() {
+        let (key, h) = falcon_keypair();
+        assert_eq!(h.coefficients.len(), 512);
+
+        let message = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+        let signature = key.sign(message);
+        assert!(key.public_key().verify(message, &signature));
+
+        // the signature carries the same public key polynomial
+        let signature_h: Vec<Felt> = signature
+            .pk_poly()
+            .0
+            .coefficients
+            .iter()
+            .map(|c| Felt::from(c.value() as u16))
+            .collect();
+        assert_eq!(h.coefficients, signature_h);
+
+        let s2 = Polynomial::new(
+            signature
+                .sig_poly()
+                .coefficients
+                .iter()
+                .map(|c| Felt::from(c.value() as u16))
+                .collect(),
+        );
+        let advice_stack = generate_advice_stack_from_signature(h.clone(), s2);
+        assert_eq!(advice_stack.len(), advice_stack_len());
+        let stacked_h: Vec<u64> = h.coefficients.iter().map(|c| c.as_int()).collect();
+        assert_eq!(&advice_stack[2..514], stacked_h.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_check_store_integrity() {
+        let endpoint = Endpoint::localhost();
+        let store_path = "./integrity_store.sqlite3";
+        let client = instantiate_client(endpoint, Some(store_path))
+            .await
+            .unwrap();
+        drop(client);
+
+        check_store_integrity(store_path).unwrap();
+        assert!(check_store_integrity("./missing_store.sqlite3").is_err());
+
+        delete_keystore_and_store(Some(store_path)).await;
+    }
+
+    #[tokio::test]
+    async fn test_encode_decode_aux() {
+        let routing = RoutingInfo {
+            version: u8::MAX,
+            channel: 0xbeef,
+            destination: u32::MAX,
+        };
+        let aux = encode_aux(routing);
+        assert_eq!(aux.as_int(), 0x00ff_beef_ffff_ffff);
+        assert_eq!(decode_aux(aux), routing);
+        assert_eq!(
+            decode_aux(encode_aux(RoutingInfo::default())),
+            RoutingInfo::default()
+        );
+
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+        let note = create_exact_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Public,
+            aux,
+            Word::default(),
+        )
+        .unwrap();
+        assert_eq!(decode_aux(note.metadata().aux()), routing);
+    }
+
+    #[tokio::test]
+    async fn test_create_note_retries_on_serial_collision() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let script = WellKnownNote::P2ID.script();
+        let serial_num = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+
+        let first = create_note_from_script_with_serials(
+            &mut client,
+            script.clone(),
+            &account,
+            None,
+            None,
+            NoteType::Public,
+            |_| serial_num,
+            0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.serial_num(), serial_num);
+
+        // the first serial collides with the note above, the second one is fresh
+        let fresh_serial = Word::from([Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)]);
+        let mut serials = vec![serial_num, fresh_serial].into_iter();
+        let second = create_note_from_script_with_serials(
+            &mut client,
+            script.clone(),
+            &account,
+            None,
+            None,
+            NoteType::Public,
+            |_| serials.next().unwrap(),
+            1,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.serial_num(), fresh_serial);
+        assert_ne!(second.id(), first.id());
+
+        // once the retries run out the collision is reported instead of submitted
+        let result = create_note_from_script_with_serials(
+            &mut client,
+            script,
+            &account,
+            None,
+            None,
+            NoteType::Public,
+            |_| serial_num,
+            1,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::NoteIdCollision(note_id)) if note_id == first.id()
+        ));
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_import_notes_from_dir() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let sender = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        let target = AccountId::from_hex("0x1478f6f84363ed200009ce915221a6").unwrap();
+
+        let dir = std::env::temp_dir().join("miden_import_notes");
+        std::fs::create_dir_all(&dir).unwrap();
+        for i in 0..2u64 {
+            let serial_num = Word::from([Felt::new(i), Felt::new(1), Felt::new(2), Felt::new(3)]);
+            let note = create_exact_p2id_note(
+                sender,
+                target,
+                vec![],
+                NoteType::Public,
+                Felt::new(0),
+                serial_num,
+            )
+            .unwrap();
+            let note_file = NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            };
+            std::fs::write(dir.join(format!("note_{i}.bin")), note_file.to_bytes()).unwrap();
+        }
+        std::fs::write(dir.join("README.txt"), "not a note").unwrap();
+
+        let imported = import_notes_from_dir(&mut client, &dir).await.unwrap();
+        assert_eq!(imported, 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_submit_pipeline() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet_a = create_basic_faucet(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet_b = create_basic_faucet(&mut client, keystore).await.unwrap();
+
+        let mut requests = Vec::new();
+        for faucet in [&faucet_a, &faucet_b, &faucet_a, &faucet_b] {
+            let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+            let mint_req = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+                .unwrap();
+            requests.push((faucet.id(), mint_req));
+        }
+
+        let results = submit_pipeline(&mut client, requests, 2).await;
+        assert_eq!(results.len(), 4);
+        let tx_ids: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+        for (i, tx_id) in tx_ids.iter().enumerate() {
+            assert!(!tx_ids[i + 1..].contains(tx_id));
+        }
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_custom_account_registers_library() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let libraries = AccountLibraries::new();
+
+        let (account, library) = create_custom_account(
+            &mut client,
+            account_code,
+            library_path,
+            vec![StorageSlot::empty_value()],
+            &libraries,
+        )
+        .await
+        .unwrap();
+
+        let registered = libraries.library_for_account(account.id()).unwrap();
+        assert_eq!(registered.digest(), library.digest());
+        assert_eq!(libraries.len(), 1);
+
+        let other = AccountId::from_hex("0x4eef4d8ee35714200009819615ca84").unwrap();
+        assert!(libraries.library_for_account(other).is_none());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_simulate_at_sync_height() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let tip = client.get_sync_height().await.unwrap();
+        let tx_req = TransactionRequestBuilder::new().build().unwrap();
+        let (tx_result, block) = simulate_at_sync_height(&mut client, account.id(), tx_req)
+            .await
+            .unwrap();
+        assert_eq!(block, tip);
+        assert_eq!(tx_result.executed_transaction().account_id(), account.id());
+        assert_eq!(
+            tx_result.executed_transaction().block_header().block_num(),
+            tip
+        );
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_assert_no_dangling_notes() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+
+        mint_from_faucet_for_account(&mut client, &account, &faucet, 100, None)
+            .await
+            .unwrap();
+        assert_no_dangling_notes(&mut client, &account)
+            .await
+            .unwrap();
+
+        // a minted note the account never consumes is left behind
+        let notes = mint_series(
+            &mut client,
+            &faucet,
+            &[(account.id(), 50)],
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        match assert_no_dangling_notes(&mut client, &account).await {
+            Err(ToolsError::DanglingNotes { unconsumed, .. }) => {
+                assert_eq!(unconsumed, vec![notes[0].id()]);
+            }
+            result => panic!("expected dangling notes, got {result:?}"),
+        }
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_record_to_note() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+
+        let notes = mint_series(
+            &mut client,
+            &faucet,
+            &[(account.id(), 75)],
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        client.sync_state().await.unwrap();
+
+        let record = client.get_input_note(notes[0].id()).await.unwrap().unwrap();
+        let note = record_to_note(&record).unwrap();
+        assert_eq!(note.id(), notes[0].id());
+        assert_eq!(note.assets(), notes[0].assets());
+        assert_eq!(note.metadata(), notes[0].metadata());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_env() {
+        let endpoint = Endpoint::localhost();
+        let store_path = "./custom_env.sqlite3";
+        let keystore_path = "./custom_keystore";
+
+        let keystore = FilesystemKeyStore::new(keystore_path.into()).unwrap();
+        let config = ClientConfig::new(endpoint).with_store_path(store_path);
+        let mut client = instantiate_client_with_authenticator(config, keystore.clone())
+            .await
+            .unwrap();
+        client.sync_state().await.unwrap();
+        create_basic_account(&mut client, keystore).await.unwrap();
+        drop(client);
+        assert!(Path::new(keystore_path).exists());
+
+        delete_env(store_path, keystore_path).await;
+        assert!(!Path::new(store_path).exists());
+        assert!(!Path::new(keystore_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_note_watcher() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore.clone())
+            .await
+            .unwrap();
+        let faucet = create_basic_faucet(&mut client, keystore).await.unwrap();
+
+        let mut watcher = NoteWatcher::new(Some(account.id()));
+        assert!(watcher.poll(&mut client).await.unwrap().is_empty());
+
+        let notes = mint_series(
+            &mut client,
+            &faucet,
+            &[(account.id(), 10)],
+            NoteType::Public,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            watcher.poll(&mut client).await.unwrap(),
+            vec![notes[0].id()]
+        );
+
+        // nothing changed since the previous poll
+        assert!(watcher.poll(&mut client).await.unwrap().is_empty());
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_custom_account_checked() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let account_code = fs::read_to_string(Path::new("./masm/accounts/counter.masm")).unwrap();
+        let library_path = "external_contract::counter_contract";
+        let libraries = AccountLibraries::new();
+
+        let (reference, _) = create_custom_account(
+            &mut client,
+            account_code.clone(),
+            library_path,
+            vec![StorageSlot::empty_value()],
+            &libraries,
+        )
+        .await
+        .unwrap();
+        let expected = reference.code().commitment();
+
+        let (account, _) = create_custom_account_checked(
+            &mut client,
+            account_code.clone(),
+            library_path,
+            vec![StorageSlot::empty_value()],
+            &libraries,
+            expected,
+        )
+        .await
+        .unwrap();
+        assert_eq!(account.code().commitment(), expected);
+
+        let result = create_custom_account_checked(
+            &mut client,
+            account_code,
+            library_path,
+            vec![StorageSlot::empty_value()],
+            &libraries,
+            Word::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ToolsError::CodeCommitmentMismatch { actual, .. }) if actual == expected
+        ));
+        assert_eq!(libraries.len(), 2);
+
+        delete_keystore_and_store(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_list_pending_transactions() {
+        let endpoint = Endpoint::localhost();
+        let mut client = instantiate_client(endpoint, None).await.unwrap();
+        client.sync_state().await.unwrap();
+
+        let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+        let (account, _) = create_basic_account(&mut client, keystore).await.unwrap();
+
+        let tx_req = TransactionRequestBuilder::new().build().unwrap();
+        let tx_result = client.new_transaction(account.id(), tx_req).await.unwrap();
+        let tx_id = tx_result.executed_transaction().id();
+        client.submit_transaction(tx_result).await.unwrap();
+
+        let pending = list_pending_transactions(&mut client).await.unwrap();
+        assert!(pending.iter().any(|record| record.id == tx_id));
+
+        delete_keystore_and_store(None).await;
+    }
 }